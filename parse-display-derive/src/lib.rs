@@ -14,22 +14,22 @@ mod syn_utils;
 mod bound;
 mod format_syntax;
 mod parser_builder;
+mod scan;
 
 use crate::{format_syntax::*, syn_utils::*};
 use bound::{Bound, Bounds};
 use parser_builder::{ParseVariantCode, ParserBuilder};
 use proc_macro2::{Span, TokenStream};
 use quote::{ToTokens, format_ident, quote, quote_spanned};
-use regex_syntax::escape;
 use std::{
     collections::BTreeMap,
     fmt::{Display, Formatter},
 };
-use structmeta::{Flag, StructMeta, ToTokens};
+use structmeta::{Flag, NameArgs, NameValue, StructMeta, ToTokens};
 use syn::{
     Attribute, Data, DataEnum, DataStruct, DeriveInput, Expr, Field, Fields, FieldsNamed,
-    FieldsUnnamed, GenericArgument, Ident, LitStr, Member, Path, PathArguments, Result, Token,
-    Type, Variant,
+    FieldsUnnamed, GenericArgument, Ident, LitInt, LitStr, Member, Path, PathArguments, Result,
+    Token, Type, Variant, WherePredicate,
     ext::IdentExt,
     parse::{Parse, ParseStream},
     parse_macro_input, parse_quote, parse_str,
@@ -48,27 +48,34 @@ pub fn derive_display(input: proc_macro::TokenStream) -> proc_macro::TokenStream
 
 fn derive_display_for_struct(input: &DeriveInput, data: &DataStruct) -> Result<TokenStream> {
     let hattrs = HelperAttributes::from(&input.attrs, false)?;
-    let vb = VarBase::Struct { data };
-
-    let mut format = hattrs.format;
-    if format.is_none() {
-        format = DisplayFormat::from_newtype_struct(data);
-    }
-    let Some(format) = format else {
-        bail!(
-            input.span(),
-            r#"`#[display("format")]` is required except newtype pattern."#,
-        )
+    let vb = VarBase::Struct {
+        data,
+        style: hattrs.style.clone().unwrap_or(DisplayStyle::None),
     };
-    let mut bounds = Bounds::from_data(hattrs.bound_display);
+
+    let mut bounds = Bounds::from_data(hattrs.bound_display.clone());
     let generics = &GenericParamSet::new(&input.generics);
     let cx = CodeContext {
         generics,
         crate_path: &hattrs.crate_path,
     };
-    let write = format
-        .format_args(&vb, &None, &mut bounds, &cx)?
-        .build_write(quote!(f))?;
+    let write = if let Some(kv) = &hattrs.kv {
+        build_kv_write(kv, &hattrs, &vb, &mut bounds, &cx)?
+    } else {
+        let mut format = hattrs.format.clone();
+        if format.is_none() {
+            format = DisplayFormat::from_newtype_struct(data);
+        }
+        let Some(format) = format else {
+            bail!(
+                input.span(),
+                r#"`#[display("format")]` is required except newtype pattern."#,
+            )
+        };
+        format
+            .format_args(&vb, &None, &mut bounds, &cx)?
+            .build_write(quote!(f))?
+    };
     let trait_path = parse_quote!(::core::fmt::Display);
     let wheres = bounds.build_wheres(&trait_path);
     impl_trait_result(
@@ -83,6 +90,69 @@ fn derive_display_for_struct(input: &DeriveInput, data: &DataStruct) -> Result<T
         hattrs.dump_display,
     )
 }
+fn build_kv_write(
+    kv: &Kv,
+    hattrs: &HelperAttributes,
+    vb: &VarBase,
+    bounds: &mut Bounds,
+    cx: &CodeContext,
+) -> Result<TokenStream> {
+    if let Some(format) = &hattrs.format {
+        bail!(
+            format.span,
+            "`#[display(\"...\")]` cannot be specified with `#[display(kv)]`."
+        );
+    }
+    let Some(fields) = vb.fields() else {
+        unreachable!()
+    };
+    let fields = kv_fields(kv, fields)?;
+    if fields.is_empty() {
+        return Ok(quote! {
+            let _ = f;
+            ::core::result::Result::Ok(())
+        });
+    }
+    let crate_path = cx.crate_path;
+    let mut entries = Vec::new();
+    for (key, field) in fields {
+        let hattrs_field = HelperAttributes::from(&field.attrs, false)?;
+        hattrs_field.check_kv_field()?;
+        let name = vb.styled_name_of(&key);
+        let value = vb.format_arg_of_field(
+            &key,
+            field,
+            &FormatSpec::default(),
+            field.span(),
+            bounds,
+            cx,
+        )?;
+        let entry = quote! { w.entry(#name, &#value)?; };
+        entries.push(
+            if hattrs_field.opt.value() && hattrs_field.opt_none.is_empty() {
+                quote! {
+                    if ::core::option::Option::is_some(&self.#key) {
+                        #entry
+                    }
+                }
+            } else if let Some(omit) = hattrs_field.omit_cond(&quote!(self.#key)) {
+                quote! {
+                    if !#omit {
+                        #entry
+                    }
+                }
+            } else {
+                entry
+            },
+        );
+    }
+    let Kv { sep, assign, .. } = kv;
+    Ok(quote! {
+        let mut w = #crate_path::helpers::KvWriter::new(f, #sep, #assign);
+        #(#entries)*
+        ::core::result::Result::Ok(())
+    })
+}
 fn derive_display_for_enum(input: &DeriveInput, data: &DataEnum) -> Result<TokenStream> {
     fn make_arm(
         hattrs_enum: &HelperAttributes,
@@ -108,8 +178,13 @@ fn derive_display_for_enum(input: &DeriveInput, data: &DataEnum) -> Result<Token
             Fields::Unit => quote! {},
         };
         let hattrs_variant = HelperAttributes::from(&variant.attrs, false)?;
+        hattrs_variant.check_no_kv()?;
         let style = DisplayStyle::from_helper_attributes(hattrs_enum, &hattrs_variant);
         let mut format = hattrs_variant.format;
+        if format.is_none() && has_from_str_other(&variant.attrs)? {
+            let (key, _) = other_variant_field(variant)?;
+            format = Some(DisplayFormat::parse(&format!("{{{key}}}"), variant.span())?);
+        }
         if format.is_none() {
             format.clone_from(&hattrs_enum.format);
         }
@@ -138,6 +213,7 @@ fn derive_display_for_enum(input: &DeriveInput, data: &DataEnum) -> Result<Token
         })
     }
     let hattrs = HelperAttributes::from(&input.attrs, false)?;
+    hattrs.check_no_kv()?;
     let mut bounds = Bounds::from_data(hattrs.bound_display.clone());
     let mut arms = Vec::new();
     let generics = &GenericParamSet::new(&input.generics);
@@ -169,8 +245,17 @@ pub fn derive_from_str(input: proc_macro::TokenStream) -> proc_macro::TokenStrea
         Data::Union(_) => panic!("`#[derive(FromStr)]` supports only enum or struct."),
     })
 }
+#[proc_macro]
+pub fn scan(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as scan::ScanInput);
+    into_macro_output(scan::expand_scan(input))
+}
+
 fn derive_from_str_for_struct(input: &DeriveInput, data: &DataStruct) -> Result<TokenStream> {
     let hattrs = HelperAttributes::from(&input.attrs, true)?;
+    if let Some(span) = hattrs.other.span {
+        bail!(span, "`#[from_str(other)]` cannot be specified for struct.");
+    }
     let p = ParserBuilder::from_struct(&hattrs, data)?;
     let crate_path = &hattrs.crate_path;
     let warnings = hattrs.deprecated_default_fields_warnings();
@@ -179,14 +264,16 @@ fn derive_from_str_for_struct(input: &DeriveInput, data: &DataStruct) -> Result<
     let generics = GenericParamSet::new(&input.generics);
     let mut bounds = Bounds::from_data(hattrs.bound_from_str_resolved());
     p.build_bounds(&generics, &mut bounds)?;
+    push_from_str_error_bounds(&hattrs, &mut bounds);
     let wheres = bounds.build_wheres(&trait_path);
+    let err_ty = hattrs.err_type();
     let mut ts = TokenStream::new();
     ts.extend(impl_trait(
         input,
         &trait_path,
         &wheres,
         quote! {
-            type Err = #crate_path::ParseError;
+            type Err = #err_ty;
             fn from_str(s: &str) -> ::core::result::Result<Self, Self::Err> {
                 #warnings
                 #body
@@ -207,14 +294,50 @@ fn derive_from_str_for_struct(input: &DeriveInput, data: &DataStruct) -> Result<
             },
         ));
     }
+    let hattrs_infer = HelperAttributes {
+        regex_infer: true,
+        ..hattrs.clone()
+    };
+    let mut parsers = Vec::new();
+    let mut bounds = Bounds::from_data(hattrs.bound_from_str_resolved());
+    if hattrs.prefix.value() {
+        let p = ParserBuilder::from_struct(&hattrs_infer, data)?;
+        p.build_bounds(&generics, &mut bounds)?;
+        parsers.push((parse_quote!(Self), p));
+    }
+    push_from_str_error_bounds(&hattrs, &mut bounds);
+    let wheres_prefix = bounds.build_wheres(&parse_quote!(#crate_path::FromStrRegex));
+    ts.extend(impl_from_str_prefix(
+        input,
+        &hattrs,
+        &parsers,
+        &wheres_prefix,
+    )?);
+    ts.extend(impl_from_str_find(
+        input,
+        &hattrs,
+        &[(parse_quote!(Self), p)],
+        &wheres,
+    )?);
     dump_if(hattrs.dump_from_str, &ts);
     Ok(ts)
 }
 fn derive_from_str_for_enum(input: &DeriveInput, data: &DataEnum) -> Result<TokenStream> {
     let hattrs_enum = HelperAttributes::from(&input.attrs, true)?;
+    hattrs_enum.check_no_kv()?;
+    hattrs_enum.check_no_any_order()?;
     if let Some(span) = hattrs_enum.default_self {
         bail!(span, "`#[from_str(default)]` cannot be specified for enum.");
     }
+    if let Some(span) = hattrs_enum.other.span {
+        bail!(span, "`#[from_str(other)]` cannot be specified for enum.");
+    }
+    if let Some(alias) = hattrs_enum.aliases.first() {
+        bail!(
+            alias.span(),
+            "`#[from_str(alias = ...)]` cannot be specified for enum."
+        );
+    }
     let crate_path = &hattrs_enum.crate_path;
     let trait_path = parse_quote!(::core::str::FromStr);
     let mut bounds = Bounds::from_data(hattrs_enum.bound_from_str_resolved());
@@ -223,10 +346,30 @@ fn derive_from_str_for_enum(input: &DeriveInput, data: &DataEnum) -> Result<Toke
     let mut arms = Vec::new();
     let mut regex_fmts = Vec::new();
     let mut regex_args = Vec::new();
+    let mut expected = Some(Vec::new());
+    let mut other = None;
     let mut deprecated_default_fields_warning_spans =
         hattrs_enum.deprecated_default_fields_warning_spans.clone();
+    let hattrs_enum_infer = HelperAttributes {
+        regex_infer: true,
+        ..hattrs_enum.clone()
+    };
+    let mut parsers = Vec::new();
+    let mut find_parsers = Vec::new();
+    let mut bounds_infer = Bounds::from_data(hattrs_enum.bound_from_str_resolved());
     for variant in &data.variants {
         let hattrs_variant = HelperAttributes::from(&variant.attrs, true)?;
+        hattrs_variant.check_no_kv()?;
+        hattrs_variant.check_no_any_order()?;
+        if let Some(span) = hattrs_variant.prefix.span {
+            bail!(
+                span,
+                "`#[from_str(prefix)]` cannot be specified for variant."
+            );
+        }
+        if let Some(span) = hattrs_variant.find.span {
+            bail!(span, "`#[from_str(find)]` cannot be specified for variant.");
+        }
         deprecated_default_fields_warning_spans.extend(
             hattrs_variant
                 .deprecated_default_fields_warning_spans
@@ -236,16 +379,90 @@ fn derive_from_str_for_enum(input: &DeriveInput, data: &DataEnum) -> Result<Toke
             continue;
         }
         let variant_ident = &variant.ident;
-        let constructor = parse_quote!(Self::#variant_ident);
+        if let Some(span) = hattrs_variant.other.span {
+            if other.is_some() {
+                bail!(
+                    span,
+                    "`#[from_str(other)]` can be specified for only one variant."
+                );
+            }
+            if hattrs_variant.format.is_some() || hattrs_variant.regex.is_some() {
+                bail!(
+                    span,
+                    "`#[from_str(other)]` cannot be specified with the format of the variant."
+                );
+            }
+            for (name, flag) in [("prefix", &hattrs_enum.prefix), ("find", &hattrs_enum.find)] {
+                if flag.value() {
+                    bail!(
+                        span,
+                        "`#[from_str(other)]` cannot be specified with `#[from_str({name})]`."
+                    );
+                }
+            }
+            let (key, field) = other_variant_field(variant)?;
+            let ty = &field.ty;
+            let mut bounds = bounds.child(hattrs_variant.bound_from_str_resolved());
+            if bounds.can_extend && generics.contains_in_type(ty) {
+                bounds.ty.push(ty.clone());
+            }
+            other = Some(quote! {
+                if let ::core::result::Result::Ok(value) = <#ty as ::core::str::FromStr>::from_str(s) {
+                    return ::core::result::Result::Ok(Self::#variant_ident { #key: value });
+                }
+            });
+            continue;
+        }
+        let constructor: Path = parse_quote!(Self::#variant_ident);
+        if hattrs_enum.prefix.value() {
+            let p = ParserBuilder::from_variant(&hattrs_variant, &hattrs_enum_infer, variant)?;
+            let mut bounds = bounds_infer.child(hattrs_variant.bound_from_str_resolved());
+            p.build_bounds(&generics, &mut bounds)?;
+            parsers.push((constructor.clone(), p));
+        }
         let p = ParserBuilder::from_variant(&hattrs_variant, &hattrs_enum, variant)?;
         let mut bounds = bounds.child(hattrs_variant.bound_from_str_resolved());
         p.build_bounds(&generics, &mut bounds)?;
-        match p.build_parse_variant_code(constructor)? {
+        if let Some(list) = &mut expected {
+            if let Some(literals) = p.literals() {
+                for s in literals {
+                    if !list.iter().any(|x| x == s) {
+                        list.push(s.to_string());
+                    }
+                }
+            } else {
+                expected = None;
+            }
+        }
+        match p.build_parse_variant_code(constructor.clone(), &variant_ident.unraw().to_string())? {
             ParseVariantCode::MatchArm(arm) => arms.push(arm),
             ParseVariantCode::Statement(body) => bodys.push(body),
         }
         p.build_regex_fmts_args(&mut regex_fmts, &mut regex_args)?;
+        if hattrs_enum.find.value() {
+            find_parsers.push((constructor, p));
+        }
+    }
+    if other.is_some() {
+        regex_fmts.push(Some("(?s:.*?)".into()));
+    }
+    let error_var = if bodys.is_empty() {
+        quote! {}
+    } else {
+        quote! { let mut error = ::core::option::Option::None; }
+    };
+    let mut parse_error = quote! { #crate_path::ParseError::new() };
+    if let Some(expected) = expected.filter(|e| !e.is_empty()) {
+        parse_error = quote! { #parse_error.with_expected(&[#(#expected),*], s) };
     }
+    let error_expr = match (bodys.is_empty(), hattrs_enum.error.is_some()) {
+        (true, false) => parse_error,
+        (false, false) => quote! { error.unwrap_or_else(|| #parse_error) },
+        (true, true) => quote! { ::core::convert::From::from(#parse_error) },
+        (false, true) => quote! {
+            error.unwrap_or_else(|| ::core::convert::From::from(#parse_error))
+        },
+    };
     let match_body = if arms.is_empty() {
         quote! {}
     } else {
@@ -256,22 +473,26 @@ fn derive_from_str_for_enum(input: &DeriveInput, data: &DataEnum) -> Result<Toke
             }
         }
     };
+    push_from_str_error_bounds(&hattrs_enum, &mut bounds);
     let wheres = bounds.build_wheres(&trait_path);
     let warnings =
         deprecated_default_fields_warnings(crate_path, &deprecated_default_fields_warning_spans);
 
+    let err_ty = hattrs_enum.err_type();
     let mut ts = TokenStream::new();
     ts.extend(impl_trait(
         input,
         &trait_path,
         &wheres,
         quote! {
-            type Err = #crate_path::ParseError;
+            type Err = #err_ty;
             fn from_str(s: &str) -> ::core::result::Result<Self, Self::Err> {
                 #warnings
                 #match_body
+                #error_var
                 #({ #bodys })*
-                ::core::result::Result::Err(#crate_path::ParseError::new())
+                #other
+                ::core::result::Result::Err(#error_expr)
             }
         },
     ));
@@ -279,7 +500,7 @@ fn derive_from_str_for_enum(input: &DeriveInput, data: &DataEnum) -> Result<Toke
         let body = if regex_args.is_empty() {
             let fmts = regex_fmts
                 .into_iter()
-                .map(|s| escape(&s.unwrap()))
+                .map(|s| s.unwrap())
                 .collect::<Vec<_>>();
             let s = fmts.join("|");
             quote! { #s.into() }
@@ -287,7 +508,7 @@ fn derive_from_str_for_enum(input: &DeriveInput, data: &DataEnum) -> Result<Toke
             let fmts = regex_fmts
                 .into_iter()
                 .map(|s| match s {
-                    Some(s) => format!("({})", escape_fmt(&escape(&s))),
+                    Some(s) => format!("({})", escape_fmt(&s)),
                     None => "{}".to_string(),
                 })
                 .collect::<Vec<_>>();
@@ -306,10 +527,98 @@ fn derive_from_str_for_enum(input: &DeriveInput, data: &DataEnum) -> Result<Toke
             },
         ));
     }
+    push_from_str_error_bounds(&hattrs_enum, &mut bounds_infer);
+    let wheres_prefix = bounds_infer.build_wheres(&parse_quote!(#crate_path::FromStrRegex));
+    ts.extend(impl_from_str_prefix(
+        input,
+        &hattrs_enum,
+        &parsers,
+        &wheres_prefix,
+    )?);
+    ts.extend(impl_from_str_find(
+        input,
+        &hattrs_enum,
+        &find_parsers,
+        &wheres,
+    )?);
     dump_if(hattrs_enum.dump_from_str, &ts);
     Ok(ts)
 }
 
+/// Implements `FromStrPrefix`, if `#[from_str(prefix)]` is specified.
+///
+/// `parsers` are the constructors and the parsers of the struct or the variants, which infer the regexes of the fields from their types.
+fn impl_from_str_prefix(
+    input: &DeriveInput,
+    hattrs: &HelperAttributes,
+    parsers: &[(Path, ParserBuilder)],
+    wheres: &[WherePredicate],
+) -> Result<TokenStream> {
+    let Some(span) = hattrs.prefix.span else {
+        return Ok(quote! {});
+    };
+    if !cfg!(feature = "std") {
+        bail!(span, "`#[from_str(prefix)]` requires the `std` feature.");
+    }
+    let crate_path = &hattrs.crate_path;
+    let generic = !input.generics.params.is_empty();
+    let mut bodys = Vec::new();
+    for (constructor, p) in parsers {
+        bodys.push(p.build_prefix_code(constructor.clone(), generic, span)?);
+    }
+    let mut error_expr = quote! { #crate_path::ParseError::new() };
+    if hattrs.error.is_some() {
+        error_expr = quote! { ::core::convert::From::from(#error_expr) };
+    }
+    Ok(impl_trait(
+        input,
+        &parse_quote!(#crate_path::FromStrPrefix),
+        wheres,
+        quote! {
+            fn parse_prefix(s: &str) -> ::core::result::Result<(Self, &str), Self::Err> {
+                let mut error = ::core::option::Option::None;
+                #({ #bodys })*
+                ::core::result::Result::Err(error.unwrap_or_else(|| #error_expr))
+            }
+        },
+    ))
+}
+
+/// Implements `FromStrFind`, if `#[from_str(find)]` is specified.
+///
+/// `parsers` are the constructors and the parsers used by `FromStr` for the struct or the variants.
+fn impl_from_str_find(
+    input: &DeriveInput,
+    hattrs: &HelperAttributes,
+    parsers: &[(Path, ParserBuilder)],
+    wheres: &[WherePredicate],
+) -> Result<TokenStream> {
+    let Some(span) = hattrs.find.span else {
+        return Ok(quote! {});
+    };
+    if !cfg!(feature = "std") {
+        bail!(span, "`#[from_str(find)]` requires the `std` feature.");
+    }
+    let crate_path = &hattrs.crate_path;
+    let generic = !input.generics.params.is_empty();
+    let mut bodys = Vec::new();
+    for (constructor, p) in parsers {
+        bodys.push(p.build_find_code(constructor.clone(), generic, span)?);
+    }
+    Ok(impl_trait(
+        input,
+        &parse_quote!(#crate_path::FromStrFind),
+        wheres,
+        quote! {
+            fn captures_at(s: &str, start: usize) -> ::core::option::Option<#crate_path::FindCaptures<'_, Self>> {
+                let mut found: ::core::option::Option<#crate_path::FindCaptures<'_, Self>> = ::core::option::Option::None;
+                #({ #bodys })*
+                found
+            }
+        },
+    ))
+}
+
 fn get_newtype_field(data: &DataStruct) -> Option<String> {
     let fields: Vec<_> = data.fields.iter().collect();
     if fields.len() == 1 {
@@ -345,14 +654,23 @@ struct DisplayArgs {
     #[struct_meta(unnamed)]
     format: Option<LitStr>,
     with: Option<Expr>,
-    opt: Flag,
+    opt: Option<NameValue<Option<LitStr>>>,
+    omit_if: Option<Expr>,
+    opt_empty: Flag,
     style: Option<LitStr>,
     bound: Option<Vec<Quotable<Bound>>>,
     #[struct_meta(name = "crate")]
     crate_path: Option<Path>,
+    kv: Option<NameArgs<Option<KvArgs>>>,
     dump: bool,
 }
 
+#[derive(StructMeta)]
+struct KvArgs {
+    sep: Option<LitStr>,
+    assign: Option<LitStr>,
+}
+
 #[derive(Clone, ToTokens)]
 struct DefaultField(Member);
 
@@ -370,8 +688,19 @@ impl Parse for DefaultField {
 struct FromStrArgs {
     regex: Option<LitStr>,
     regex_infer: Flag,
+    strict: Flag,
+    ignore_case: Flag,
+    alias: Option<LitStr>,
+    aliases: Option<ArrayOf<LitStr>>,
+    other: Flag,
+    ignore_unknown_keys: Flag,
+    any_order: Option<NameValue<Option<LitStr>>>,
+    backtrack: Option<NameValue<Option<LitInt>>>,
+    prefix: Flag,
+    find: Flag,
     with: Option<Expr>,
     new: Option<Expr>,
+    error: Option<Path>,
     bound: Option<Vec<Quotable<Bound>>>,
     default: Flag,
     default_fields: Option<Vec<Quotable<DefaultField>>>,
@@ -384,15 +713,30 @@ struct HelperAttributes {
     format: Option<DisplayFormat>,
     with: Option<Expr>,
     opt: Flag,
+    /// The string displayed for `None` of `#[display(opt = "...")]`.
+    opt_none: String,
+    omit_if: Option<Expr>,
+    opt_empty: Flag,
     style: Option<DisplayStyle>,
     bound_display: Option<Vec<Bound>>,
     bound_from_str: Option<Vec<Bound>>,
     regex: Option<LitStr>,
     regex_infer: bool,
+    strict: bool,
+    ignore_case: Flag,
+    aliases: Vec<LitStr>,
+    other: Flag,
+    kv: Option<Kv>,
+    ignore_unknown_keys: Flag,
+    any_order: Option<NameValue<Option<LitStr>>>,
+    backtrack: Option<NameValue<Option<LitInt>>>,
+    prefix: Flag,
+    find: Flag,
     default_self: Option<Span>,
     default_fields: Vec<DefaultField>,
     deprecated_default_fields_warning_spans: Vec<Span>,
     new_expr: Option<Expr>,
+    error: Option<Path>,
     ignore: Flag,
     dump_display: bool,
     dump_from_str: bool,
@@ -404,12 +748,26 @@ impl HelperAttributes {
             format: None,
             with: None,
             opt: Flag::NONE,
+            opt_none: String::new(),
+            omit_if: None,
+            opt_empty: Flag::NONE,
             style: None,
             bound_display: None,
             bound_from_str: None,
             regex: None,
             regex_infer: false,
+            strict: false,
+            ignore_case: Flag::NONE,
+            aliases: Vec::new(),
+            other: Flag::NONE,
+            kv: None,
+            ignore_unknown_keys: Flag::NONE,
+            any_order: None,
+            backtrack: None,
+            prefix: Flag::NONE,
+            find: Flag::NONE,
             new_expr: None,
+            error: None,
             default_self: None,
             default_fields: Vec::new(),
             deprecated_default_fields_warning_spans: Vec::new(),
@@ -436,8 +794,17 @@ impl HelperAttributes {
         if let Some(with) = args.with {
             self.with = Some(with);
         }
-        if args.opt.value() {
-            self.opt = args.opt;
+        if let Some(opt) = &args.opt {
+            self.opt = Flag {
+                span: Some(opt.name_span),
+            };
+            self.opt_none = opt.value.as_ref().map(LitStr::value).unwrap_or_default();
+        }
+        if let Some(omit_if) = args.omit_if {
+            self.omit_if = Some(omit_if);
+        }
+        if args.opt_empty.value() {
+            self.opt_empty = args.opt_empty;
         }
         if let Some(style) = &args.style {
             self.style = Some(DisplayStyle::parse_lit_str(style)?);
@@ -453,6 +820,9 @@ impl HelperAttributes {
         if let Some(crate_path) = &args.crate_path {
             self.crate_path = crate_path.clone();
         }
+        if let Some(kv) = &args.kv {
+            self.kv = Some(Kv::new(kv)?);
+        }
         self.dump_from_str |= args.dump;
         self.dump_display |= args.dump;
         Ok(())
@@ -462,12 +832,41 @@ impl HelperAttributes {
             self.regex = Some(regex);
         }
         self.regex_infer |= args.regex_infer.value();
+        self.strict |= args.strict.value();
+        if args.ignore_case.value() {
+            self.ignore_case = args.ignore_case;
+        }
+        self.aliases.extend(args.alias);
+        if args.other.value() {
+            self.other = args.other;
+        }
+        if let Some(aliases) = args.aliases {
+            self.aliases.extend(aliases.into_iter());
+        }
+        if args.ignore_unknown_keys.value() {
+            self.ignore_unknown_keys = args.ignore_unknown_keys;
+        }
+        if let Some(any_order) = args.any_order {
+            self.any_order = Some(any_order);
+        }
+        if let Some(backtrack) = args.backtrack {
+            self.backtrack = Some(backtrack);
+        }
+        if args.prefix.value() {
+            self.prefix = args.prefix;
+        }
+        if args.find.value() {
+            self.find = args.find;
+        }
         if let Some(with) = args.with {
             self.with = Some(with);
         }
         if let Some(new) = args.new {
             self.new_expr = Some(new);
         }
+        if let Some(error) = args.error {
+            self.error = Some(error);
+        }
         if let Some(bound) = args.bound {
             let list = self.bound_from_str.get_or_insert(Vec::new());
             for bound in bound {
@@ -522,11 +921,169 @@ impl HelperAttributes {
         }
         None
     }
+    fn err_type(&self) -> TokenStream {
+        if let Some(error) = &self.error {
+            quote!(#error)
+        } else {
+            let crate_path = &self.crate_path;
+            quote!(#crate_path::ParseError)
+        }
+    }
     fn bound_from_str_resolved(&self) -> Option<Vec<Bound>> {
         self.bound_from_str
             .clone()
             .or_else(|| self.bound_display.clone())
     }
+    /// Returns an error if `#[display(kv)]` is specified for other than struct.
+    fn check_no_kv(&self) -> Result<()> {
+        if let Some(kv) = &self.kv {
+            bail!(
+                kv.span,
+                "`#[display(kv)]` can only be specified for struct."
+            );
+        }
+        if let Some(span) = self.ignore_unknown_keys.span {
+            bail!(
+                span,
+                "`#[from_str(ignore_unknown_keys)]` can only be specified for struct with `#[display(kv)]`."
+            );
+        }
+        Ok(())
+    }
+    /// Returns an error if `#[from_str(any_order)]` is specified for other than struct.
+    fn check_no_any_order(&self) -> Result<()> {
+        if let Some(any_order) = &self.any_order {
+            bail!(
+                any_order.name_span,
+                "`#[from_str(any_order)]` can only be specified for struct."
+            );
+        }
+        Ok(())
+    }
+    /// Returns the maximum number of splits tried by `#[from_str(backtrack)]`, or `None` if it is not specified.
+    fn backtrack_limit(&self) -> Result<Option<usize>> {
+        let Some(backtrack) = &self.backtrack else {
+            return Ok(None);
+        };
+        let Some(limit) = &backtrack.value else {
+            return Ok(Some(DEFAULT_BACKTRACK_LIMIT));
+        };
+        let value: usize = limit.base10_parse()?;
+        if value == 0 {
+            bail!(
+                limit.span(),
+                "the limit of `#[from_str(backtrack)]` must be greater than 0."
+            );
+        }
+        Ok(Some(value))
+    }
+    /// Returns the span of `#[from_str(backtrack)]` if it is specified.
+    fn backtrack_span(&self) -> Option<Span> {
+        self.backtrack.as_ref().map(|backtrack| backtrack.name_span)
+    }
+    /// Returns an error if `#[display(omit_if = ...)]` or `#[display(opt_empty)]` is specified with a conflicting attribute.
+    fn check_omit(&self) -> Result<()> {
+        let span = if let Some(omit_if) = &self.omit_if {
+            if let Some(span) = self.opt_empty.span {
+                bail!(
+                    span,
+                    "`#[display(opt_empty)]` cannot be specified with `#[display(omit_if = ...)]`."
+                );
+            }
+            omit_if.span()
+        } else if let Some(span) = self.opt_empty.span {
+            span
+        } else {
+            return Ok(());
+        };
+        if self.opt.value() {
+            bail!(
+                span,
+                "`#[display(omit_if = ...)]` and `#[display(opt_empty)]` cannot be specified with `#[display(opt)]`."
+            );
+        }
+        Ok(())
+    }
+    /// Returns whether the field is omitted by `#[display(omit_if = ...)]` or `#[display(opt_empty)]`.
+    fn is_omittable(&self) -> bool {
+        self.omit_if.is_some() || self.opt_empty.value()
+    }
+    /// Returns the condition to omit the field `value`, if `#[display(omit_if = ...)]` or `#[display(opt_empty)]` is specified.
+    fn omit_cond(&self, value: &TokenStream) -> Option<TokenStream> {
+        if let Some(omit_if) = &self.omit_if {
+            Some(quote!((#omit_if)(&#value)))
+        } else if self.opt_empty.value() {
+            Some(quote!((#value).is_empty()))
+        } else {
+            None
+        }
+    }
+    /// Returns an error if the attributes of a field of a struct with `#[display(kv)]` cannot be used.
+    fn check_kv_field(&self) -> Result<()> {
+        self.check_no_kv()?;
+        if let Some(format) = &self.format {
+            bail!(
+                format.span,
+                "The format of a field cannot be specified with `#[display(kv)]`."
+            );
+        }
+        if let Some(regex) = &self.regex {
+            bail!(
+                regex.span(),
+                "`#[from_str(regex = ...)]` cannot be specified for a field with `#[display(kv)]`."
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Setting of `#[display(kv(sep = "...", assign = "..."))]`.
+#[derive(Clone)]
+struct Kv {
+    sep: String,
+    assign: String,
+    span: Span,
+}
+impl Kv {
+    fn new(args: &NameArgs<Option<KvArgs>>) -> Result<Self> {
+        let mut kv = Self {
+            sep: " ".into(),
+            assign: "=".into(),
+            span: args.name_span,
+        };
+        if let Some(args) = &args.args {
+            for (value, lit) in [(&mut kv.sep, &args.sep), (&mut kv.assign, &args.assign)] {
+                if let Some(lit) = lit {
+                    *value = lit.value();
+                    if value.is_empty() {
+                        bail!(lit.span(), "empty string is not allowed.");
+                    }
+                }
+            }
+        }
+        Ok(kv)
+    }
+}
+
+/// Returns the fields of a struct with `#[display(kv)]`.
+fn kv_fields<'a>(kv: &Kv, fields: &'a Fields) -> Result<Vec<(FieldKey, &'a Field)>> {
+    match fields {
+        Fields::Named(fields) => Ok(FieldKey::from_fields_named(fields).collect()),
+        Fields::Unnamed(_) | Fields::Unit => bail!(
+            kv.span,
+            "`#[display(kv)]` can only be specified for struct with named fields."
+        ),
+    }
+}
+
+fn push_from_str_error_bounds(hattrs: &HelperAttributes, bounds: &mut Bounds) {
+    if let Some(error) = &hattrs.error {
+        for ty in &bounds.ty {
+            bounds.pred.push(parse_quote! {
+                #error : ::core::convert::From<<#ty as ::core::str::FromStr>::Err>
+            });
+        }
+    }
 }
 
 fn deprecated_default_fields_warnings(crate_path: &Path, spans: &[Span]) -> TokenStream {
@@ -541,7 +1098,7 @@ fn deprecated_default_fields_warnings(crate_path: &Path, spans: &[Span]) -> Toke
     quote! { #(#warnings)* }
 }
 
-#[derive(Copy, Clone)]
+#[derive(Clone)]
 enum DisplayStyle {
     None,
     LowerCase,
@@ -556,6 +1113,15 @@ enum DisplayStyle {
     TitleCaseHead,
     TitleCaseLower,
     TitleCaseUpper,
+    LowerDotCase,
+    LowerPathCase,
+    TrainCase,
+    Custom {
+        line_head: bool,
+        word_head: bool,
+        normal: bool,
+        sep: String,
+    },
 }
 
 impl DisplayStyle {
@@ -574,7 +1140,17 @@ impl DisplayStyle {
         \"Title Case\", \
         \"Title case\", \
         \"title case\", \
-        \"TITLE CASE\"";
+        \"TITLE CASE\", \
+        \"flatcase\", \
+        \"UPPERFLATCASE\", \
+        \"COBOL-CASE\", \
+        \"Train-Case\", \
+        \"dot.case\", \
+        \"path/case\", \
+        \"{lower}:{sep=...}\", \
+        \"{upper}:{sep=...}\", \
+        \"{title}:{sep=...}\", \
+        \"{camel}:{sep=...}\"";
         match Self::parse(&s.value()) {
             Err(_) => bail!(s.span(), "{ERROR_MESSAGE}"),
             Ok(value) => Ok(value),
@@ -596,7 +1172,31 @@ impl DisplayStyle {
             "Title case" => TitleCaseHead,
             "title case" => TitleCaseLower,
             "TITLE CASE" => TitleCaseUpper,
-            _ => return Err(ParseDisplayStyleError),
+            "flatcase" => LowerCase,
+            "UPPERFLATCASE" => UpperCase,
+            "COBOL-CASE" => UpperKebabCase,
+            "Train-Case" => TrainCase,
+            "dot.case" => LowerDotCase,
+            "path/case" => LowerPathCase,
+            _ => {
+                let Some(c) = regex!(r"^\{(lower|upper|title|camel)\}:\{sep=(.*)\}$").captures(s)
+                else {
+                    return Err(ParseDisplayStyleError);
+                };
+                let (line_head, word_head, normal) = match &c[1] {
+                    "lower" => (false, false, false),
+                    "upper" => (true, true, true),
+                    "title" => (true, true, false),
+                    "camel" => (false, true, false),
+                    _ => unreachable!(),
+                };
+                Custom {
+                    line_head,
+                    word_head,
+                    normal,
+                    sep: c[2].to_string(),
+                }
+            }
         })
     }
     fn from_helper_attributes(
@@ -605,10 +1205,12 @@ impl DisplayStyle {
     ) -> Self {
         hattrs_variant
             .style
-            .or(hattrs_enum.style)
+            .as_ref()
+            .or(hattrs_enum.style.as_ref())
+            .cloned()
             .unwrap_or(DisplayStyle::None)
     }
-    fn apply(self, ident: &Ident) -> String {
+    fn apply(&self, s: &str) -> String {
         fn convert_case(c: char, to_upper: bool) -> char {
             if to_upper {
                 c.to_ascii_uppercase()
@@ -617,10 +1219,9 @@ impl DisplayStyle {
             }
         }
 
-        let s = ident.to_string();
         let (line_head, word_head, normal, sep) = match self {
             DisplayStyle::None => {
-                return s;
+                return s.to_string();
             }
             DisplayStyle::LowerCase => (false, false, false, ""),
             DisplayStyle::UpperCase => (true, true, true, ""),
@@ -634,6 +1235,15 @@ impl DisplayStyle {
             DisplayStyle::TitleCaseUpper => (true, true, true, " "),
             DisplayStyle::TitleCaseLower => (false, false, false, " "),
             DisplayStyle::TitleCaseHead => (true, false, false, " "),
+            DisplayStyle::LowerDotCase => (false, false, false, "."),
+            DisplayStyle::LowerPathCase => (false, false, false, "/"),
+            DisplayStyle::TrainCase => (true, true, false, "-"),
+            DisplayStyle::Custom {
+                line_head,
+                word_head,
+                normal,
+                sep,
+            } => (*line_head, *word_head, *normal, sep.as_str()),
         };
         let mut is_line_head = true;
         let mut is_word_head = true;
@@ -701,6 +1311,54 @@ impl DisplayFormat {
         }
         Ok(Self { parts, span })
     }
+    /// Splits the format at `delimiter` in the literal parts.
+    fn split(&self, delimiter: &str) -> Vec<DisplayFormat> {
+        let mut segments = vec![Vec::new()];
+        for part in &self.parts {
+            if let DisplayFormatPart::Str(s) = part {
+                for (index, piece) in s.split(delimiter).enumerate() {
+                    if index != 0 {
+                        segments.push(Vec::new());
+                    }
+                    if !piece.is_empty() {
+                        let segment = segments.last_mut().unwrap();
+                        segment.push(DisplayFormatPart::Str(piece.into()));
+                    }
+                }
+            } else {
+                segments.last_mut().unwrap().push(part.clone());
+            }
+        }
+        segments
+            .into_iter()
+            .map(|parts| DisplayFormat {
+                parts,
+                span: self.span,
+            })
+            .collect()
+    }
+    /// Returns the leading symbols of the literal between the first two placeholders, used as the delimiter of `#[from_str(any_order)]`.
+    fn infer_delimiter(&self) -> Option<String> {
+        let mut parts = self.parts.iter();
+        parts.find(|part| matches!(part, DisplayFormatPart::Var { .. }))?;
+        let mut delimiter = String::new();
+        for part in parts {
+            let s = match part {
+                DisplayFormatPart::Str(s) => s,
+                DisplayFormatPart::EscapedBeginBracket => "{",
+                DisplayFormatPart::EscapedEndBracket => "}",
+                DisplayFormatPart::Var { .. } => break,
+            };
+            let len = s
+                .find(|c: char| c.is_alphanumeric() || c == '_')
+                .unwrap_or(s.len());
+            delimiter.push_str(&s[..len]);
+            if len < s.len() {
+                break;
+            }
+        }
+        (!delimiter.is_empty()).then_some(delimiter)
+    }
     fn from_newtype_struct(data: &DataStruct) -> Option<Self> {
         let p = DisplayFormatPart::Var {
             arg: get_newtype_field(data)?,
@@ -816,9 +1474,16 @@ struct CodeContext<'a> {
     crate_path: &'a Path,
 }
 
+/// The argument of the format that is replaced with the field name. (`{#name}`)
+const FIELD_NAME_ARG: &str = "#name";
+
+/// The maximum number of splits tried by `#[from_str(backtrack)]` if the limit is not specified.
+const DEFAULT_BACKTRACK_LIMIT: usize = 1000;
+
 enum VarBase<'a> {
     Struct {
         data: &'a DataStruct,
+        style: DisplayStyle,
     },
     Variant {
         variant: &'a Variant,
@@ -830,6 +1495,7 @@ enum VarBase<'a> {
         key: &'a FieldKey,
     },
     FieldSome {
+        parent: &'a VarBase<'a>,
         key: &'a FieldKey,
         ty: &'a Type,
     },
@@ -845,6 +1511,10 @@ impl VarBase<'_> {
         bounds: &mut Bounds,
         cx: &CodeContext,
     ) -> Result<TokenStream> {
+        if arg == FIELD_NAME_ARG {
+            let s = self.styled_field_name(span)?;
+            return Ok(quote! { #s });
+        }
         let keys = FieldKey::from_str_deep(arg);
         if keys.is_empty() {
             if matches!(self, VarBase::Struct { .. } | VarBase::Variant { .. })
@@ -870,7 +1540,7 @@ impl VarBase<'_> {
                     format_arg(quote!(*#ident), ty, format_spec, span, with, bounds, cx)?
                 }
                 VarBase::Variant { variant, style, .. } => {
-                    let s = style.apply(&variant.ident);
+                    let s = style.apply(&variant.ident.to_string());
                     quote! { #s }
                 }
             });
@@ -902,6 +1572,9 @@ impl VarBase<'_> {
         cx: &CodeContext,
     ) -> Result<TokenStream> {
         let hattrs = HelperAttributes::from(&field.attrs, false)?;
+        hattrs.check_no_kv()?;
+        hattrs.check_omit()?;
+        let omit = hattrs.omit_cond(&self.field_expr(key));
         let mut bounds = bounds.child(hattrs.bound_display);
         let vb = VarBase::Field {
             parent: self,
@@ -918,7 +1591,11 @@ impl VarBase<'_> {
             let crate_path = cx.crate_path;
             let in_expr = self.field_expr(key);
             let formatter_ident = Ident::new("_formatter", Span::call_site());
-            let vb = VarBase::FieldSome { key, ty: inner_ty };
+            let vb = VarBase::FieldSome {
+                parent: self,
+                key,
+                ty: inner_ty,
+            };
             let out_expr = vb.format_arg_from_some_format(
                 hattrs.format,
                 format_spec,
@@ -928,6 +1605,7 @@ impl VarBase<'_> {
                 cx,
             )?;
             let ident = key.binding_var();
+            let none_value = &hattrs.opt_none;
             Ok(quote! {
                 (
                     #crate_path::helpers::OptionFormatHelper::<#inner_ty, _> {
@@ -935,19 +1613,34 @@ impl VarBase<'_> {
                         f: |#ident : &#inner_ty, #formatter_ident : &mut ::core::fmt::Formatter| {
                              ::core::write!(#formatter_ident, "{}", #out_expr)
                         },
-                        none_value: "",
+                        none_value: #none_value,
                     }
                 )
             })
         } else {
-            vb.format_arg_from_some_format(
+            let out_expr = vb.format_arg_from_some_format(
                 hattrs.format,
                 format_spec,
                 span,
                 &hattrs.with,
                 &mut bounds,
                 cx,
-            )
+            )?;
+            let Some(omit) = omit else {
+                return Ok(out_expr);
+            };
+            let crate_path = cx.crate_path;
+            let formatter_ident = Ident::new("_formatter", Span::call_site());
+            Ok(quote! {
+                (
+                    #crate_path::helpers::OmitFormatHelper {
+                        omit: #omit,
+                        f: |#formatter_ident : &mut ::core::fmt::Formatter| {
+                             ::core::write!(#formatter_ident, "{}", #out_expr)
+                        },
+                    }
+                )
+            })
         }
     }
     fn format_arg_from_some_format(
@@ -1003,6 +1696,27 @@ impl VarBase<'_> {
             VarBase::FieldSome { ty, .. } => DisplayFormat::parse("{}", ty.span())?,
         })
     }
+    /// Returns the string for `{#name}`, which is the field name converted by the style of the struct or variant.
+    fn styled_field_name(&self, span: Span) -> Result<String> {
+        if let VarBase::Field { parent, key, .. } | VarBase::FieldSome { parent, key, .. } = self {
+            if let VarBase::Struct { .. } | VarBase::Variant { .. } = parent {
+                return Ok(parent.styled_name_of(key));
+            }
+        }
+        bail!(
+            span,
+            "`{{#name}}` can only be used in the format of a field."
+        )
+    }
+    /// Returns the name of the field `key` converted by the style of the struct or variant.
+    fn styled_name_of(&self, key: &FieldKey) -> String {
+        match self {
+            VarBase::Struct { style, .. } | VarBase::Variant { style, .. } => {
+                style.apply(&key.to_string())
+            }
+            VarBase::Field { .. } | VarBase::FieldSome { .. } => key.to_string(),
+        }
+    }
     fn fields(&self) -> Option<&Fields> {
         match self {
             VarBase::Struct { data, .. } => Some(&data.fields),
@@ -1151,6 +1865,31 @@ impl ToTokens for FieldKey {
     }
 }
 
+fn has_from_str_other(attrs: &[Attribute]) -> Result<bool> {
+    for a in attrs {
+        if a.path().is_ident("from_str") {
+            let args: FromStrArgs = a.parse_args()?;
+            if args.other.value() {
+                return Ok(true);
+            }
+        }
+    }
+    Ok(false)
+}
+
+/// Returns the field that stores the input of the `#[from_str(other)]` variant.
+fn other_variant_field(variant: &Variant) -> Result<(FieldKey, &Field)> {
+    let mut fields = variant.fields.iter();
+    if let (Some(field), None) = (fields.next(), fields.next()) {
+        Ok((FieldKey::from_field(0, field), field))
+    } else {
+        bail!(
+            variant.ident.span(),
+            "`#[from_str(other)]` variant must have exactly one field."
+        )
+    }
+}
+
 fn field_map(fields: &Fields) -> BTreeMap<FieldKey, &Field> {
     let mut m = BTreeMap::new();
     for (idx, field) in fields.iter().enumerate() {
@@ -1211,6 +1950,24 @@ fn escape_fmt(s: &str) -> String {
     out
 }
 
+const INTEGER_TYPES: &[&str] = &[
+    "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize",
+];
+const FLOAT_TYPES: &[&str] = &["f32", "f64"];
+
+/// Types other than numbers whose `Display` pads the value to the width.
+const PADDED_TYPES: &[&str] = &["bool", "char", "String"];
+
+fn is_type_of(ty: &Type, names: &[&str]) -> bool {
+    if let Type::Path(ty) = ty {
+        if ty.qself.is_none() {
+            if let Some(ident) = ty.path.get_ident() {
+                return names.iter().any(|name| ident == name);
+            }
+        }
+    }
+    false
+}
 fn get_option_element(ty: &Type) -> Option<&Type> {
     get_element(ty, &[&["std", "option"], &["core", "option"]], "Option")
 }
@@ -1,6 +1,11 @@
 use crate::{
-    Bounds, DisplayFormat, DisplayFormatPart, DisplayStyle, FieldKey, HelperAttributes, VarBase,
-    With, field_map, get_option_element, join, regex_utils::*, set_span, syn_utils::*,
+    Bounds, DisplayFormat, DisplayFormatPart, DisplayStyle, FIELD_NAME_ARG, FLOAT_TYPES, FieldKey,
+    HelperAttributes, INTEGER_TYPES, Kv, PADDED_TYPES, VarBase, With, field_map,
+    format_syntax::{Align, FormatSpec, FormatType, SubArg},
+    get_option_element, is_type_of, join, kv_fields,
+    regex_utils::*,
+    set_span,
+    syn_utils::*,
 };
 use proc_macro2::{Span, TokenStream};
 use quote::{format_ident, quote, quote_spanned};
@@ -13,8 +18,9 @@ use std::{
     collections::{BTreeMap, HashMap},
     mem,
 };
+use structmeta::NameValue;
 use syn::{
-    DataStruct, Expr, Field, Fields, Ident, LitStr, Path, Result, Variant, parse_quote,
+    DataStruct, Expr, Field, Fields, Ident, LitStr, Path, Result, Type, Variant, parse_quote,
     spanned::Spanned,
 };
 
@@ -27,14 +33,47 @@ pub(crate) struct ParserBuilder<'a> {
     use_default: bool,
     span: Span,
     new_expr: Option<Expr>,
+    ignore_case: bool,
+    aliases: Vec<String>,
+    kv: Option<KvParser>,
+    any_order: Option<AnyOrderParser>,
+    /// The maximum number of splits tried by `#[from_str(backtrack)]`.
+    backtrack: Option<usize>,
+    custom_error: bool,
     crate_path: &'a Path,
 }
 
+/// Setting of `#[display(kv)]` used to parse the key-value pairs.
+struct KvParser {
+    sep: String,
+    assign: String,
+    /// Pairs of the key and the name of the field.
+    keys: Vec<(String, String)>,
+    /// Capture names of the fields in the same order as `keys`.
+    captures: Vec<String>,
+    ignore_unknown_keys: bool,
+}
+
+/// Setting of `#[from_str(any_order)]` used to match the segments of the format in any order.
+struct AnyOrderParser {
+    delimiter: String,
+    segments: Vec<ParseFormat>,
+}
+
 impl<'a> ParserBuilder<'a> {
-    fn new(source: &'a Fields, regex_infer: bool, crate_path: &'a Path) -> Result<Self> {
+    fn new(
+        source: &'a Fields,
+        regex_infer: bool,
+        strict: bool,
+        custom_error: bool,
+        crate_path: &'a Path,
+    ) -> Result<Self> {
         let mut fields = BTreeMap::new();
         for (key, field) in field_map(source) {
-            fields.insert(key, FieldEntry::new(field, regex_infer, crate_path)?);
+            fields.insert(
+                key,
+                FieldEntry::new(field, regex_infer, strict, custom_error, crate_path)?,
+            );
         }
         Ok(Self {
             source,
@@ -45,15 +84,61 @@ impl<'a> ParserBuilder<'a> {
             use_default: false,
             span: Span::call_site(),
             new_expr: None,
+            ignore_case: false,
+            aliases: Vec::new(),
+            kv: None,
+            any_order: None,
+            backtrack: None,
+            custom_error,
             crate_path,
         })
     }
     pub fn from_struct(hattrs: &'a HelperAttributes, data: &'a DataStruct) -> Result<Self> {
-        let mut s = Self::new(&data.fields, hattrs.regex_infer, &hattrs.crate_path)?;
-        let vb = VarBase::Struct { data };
+        let mut s = Self::new(
+            &data.fields,
+            hattrs.regex_infer,
+            hattrs.strict,
+            hattrs.error.is_some(),
+            &hattrs.crate_path,
+        )?;
+        let vb = VarBase::Struct {
+            data,
+            style: hattrs.style.clone().unwrap_or(DisplayStyle::None),
+        };
         s.new_expr.clone_from(&hattrs.new_expr);
+        s.ignore_case = hattrs.ignore_case.value();
         s.apply_attrs(hattrs)?;
-        s.push_attrs(hattrs, &vb)?;
+        if let Some(kv) = &hattrs.kv {
+            if let Some(any_order) = &hattrs.any_order {
+                bail!(
+                    any_order.name_span,
+                    "`#[from_str(any_order)]` cannot be specified with `#[display(kv)]`."
+                );
+            }
+            s.set_kv(kv, hattrs, &vb)?;
+        } else if let Some(any_order) = &hattrs.any_order {
+            hattrs.check_no_kv()?;
+            s.set_any_order(any_order, hattrs, &vb)?;
+        } else {
+            hattrs.check_no_kv()?;
+            s.push_attrs(hattrs, &vb)?;
+            s.set_aliases(&hattrs.aliases)?;
+        }
+        if let Some(span) = hattrs.backtrack_span() {
+            if hattrs.kv.is_some() {
+                bail!(
+                    span,
+                    "`#[from_str(backtrack)]` cannot be specified with `#[display(kv)]`."
+                );
+            }
+            if hattrs.any_order.is_some() {
+                bail!(
+                    span,
+                    "`#[from_str(backtrack)]` cannot be specified with `#[from_str(any_order)]`."
+                );
+            }
+        }
+        s.set_backtrack(hattrs.backtrack_limit()?);
         Ok(s)
     }
     pub fn from_variant(
@@ -61,9 +146,17 @@ impl<'a> ParserBuilder<'a> {
         hattrs_enum: &'a HelperAttributes,
         variant: &'a Variant,
     ) -> Result<Self> {
+        if let Some(error) = &hattrs_variant.error {
+            bail!(
+                error.span(),
+                "`#[from_str(error = ...)]` cannot be specified for variant."
+            );
+        }
         let mut s = Self::new(
             &variant.fields,
             hattrs_enum.regex_infer || hattrs_variant.regex_infer,
+            hattrs_enum.strict || hattrs_variant.strict,
+            hattrs_enum.error.is_some(),
             &hattrs_enum.crate_path,
         )?;
         let vb = VarBase::Variant {
@@ -71,11 +164,18 @@ impl<'a> ParserBuilder<'a> {
             style: DisplayStyle::from_helper_attributes(hattrs_enum, hattrs_variant),
         };
         s.new_expr.clone_from(&hattrs_variant.new_expr);
+        s.ignore_case = hattrs_enum.ignore_case.value() || hattrs_variant.ignore_case.value();
         s.apply_attrs(hattrs_enum)?;
         s.apply_attrs(hattrs_variant)?;
         if !s.try_push_attrs(hattrs_variant, &vb)? {
             s.push_attrs(hattrs_enum, &vb)?;
         }
+        s.set_aliases(&hattrs_variant.aliases)?;
+        s.set_backtrack(
+            hattrs_variant
+                .backtrack_limit()?
+                .or(hattrs_enum.backtrack_limit()?),
+        );
         Ok(s)
     }
     fn apply_attrs(&mut self, hattrs: &HelperAttributes) -> Result<()> {
@@ -92,9 +192,135 @@ impl<'a> ParserBuilder<'a> {
         }
         Ok(())
     }
+    fn set_aliases(&mut self, aliases: &[LitStr]) -> Result<()> {
+        if let Some(alias) = aliases.first() {
+            if !matches!(self.parse_format, ParseFormat::String(_)) {
+                bail!(
+                    alias.span(),
+                    "`#[from_str(alias = ...)]` can only be specified if the format is a fixed string."
+                );
+            }
+        }
+        self.aliases = aliases.iter().map(|alias| alias.value()).collect();
+        Ok(())
+    }
+    fn set_backtrack(&mut self, limit: Option<usize>) {
+        if limit.is_some() {
+            // Backtracking tries the splits with the regex of each capture.
+            if let ParseFormat::Parts(_) = self.parse_format {
+                self.parse_format.as_hirs();
+            }
+        }
+        self.backtrack = limit;
+    }
+    fn set_kv(&mut self, kv: &Kv, hattrs: &HelperAttributes, vb: &VarBase) -> Result<()> {
+        if let Some(format) = &hattrs.format {
+            bail!(
+                format.span,
+                "`#[display(\"...\")]` cannot be specified with `#[display(kv)]`."
+            );
+        }
+        if let Some(regex) = &hattrs.regex {
+            bail!(
+                regex.span(),
+                "`#[from_str(regex = ...)]` cannot be specified with `#[display(kv)]`."
+            );
+        }
+        if let Some(alias) = hattrs.aliases.first() {
+            bail!(
+                alias.span(),
+                "`#[from_str(alias = ...)]` cannot be specified with `#[display(kv)]`."
+            );
+        }
+        let mut keys = Vec::new();
+        let mut captures = Vec::new();
+        for (key, _) in kv_fields(kv, self.source)? {
+            let e = field_of(&mut self.fields, &key, kv.span)?;
+            e.hattrs.check_kv_field()?;
+            e.kv = true;
+            captures.push(e.set_capture(&[], &mut self.capture_next));
+            keys.push((vb.styled_name_of(&key), key.to_string()));
+        }
+        self.kv = Some(KvParser {
+            sep: kv.sep.clone(),
+            assign: kv.assign.clone(),
+            keys,
+            captures,
+            ignore_unknown_keys: hattrs.ignore_unknown_keys.value(),
+        });
+        Ok(())
+    }
+    fn set_any_order(
+        &mut self,
+        any_order: &NameValue<Option<LitStr>>,
+        hattrs: &HelperAttributes,
+        vb: &VarBase,
+    ) -> Result<()> {
+        let span = any_order.name_span;
+        if hattrs.regex.is_some() {
+            bail!(
+                span,
+                "`#[from_str(any_order)]` cannot be specified with `#[from_str(regex = ...)]`."
+            );
+        }
+        if let Some(alias) = hattrs.aliases.first() {
+            bail!(
+                alias.span(),
+                "`#[from_str(alias = ...)]` cannot be specified with `#[from_str(any_order)]`."
+            );
+        }
+        let Some(format) = &hattrs.format else {
+            bail!(
+                span,
+                "`#[from_str(any_order)]` requires `#[display(\"...\")]`."
+            );
+        };
+        let delimiter = if let Some(delimiter) = &any_order.value {
+            if delimiter.value().is_empty() {
+                bail!(delimiter.span(), "empty string is not allowed.");
+            }
+            delimiter.value()
+        } else if let Some(delimiter) = format.infer_delimiter() {
+            delimiter
+        } else {
+            bail!(
+                span,
+                "cannot infer the delimiter from the format. Specify it as `#[from_str(any_order = \"...\")]`."
+            );
+        };
+        let formats = format.split(&delimiter);
+        if formats.len() < 2 {
+            bail!(
+                format.span,
+                "the format does not contain the delimiter `{delimiter}` of `#[from_str(any_order)]`."
+            );
+        }
+        let mut segments = Vec::new();
+        for format in &formats {
+            self.push_format(format, vb, None, None)?;
+            segments.push(mem::take(&mut self.parse_format));
+        }
+        self.any_order = Some(AnyOrderParser {
+            delimiter,
+            segments,
+        });
+        Ok(())
+    }
     fn field(&mut self, key: &FieldKey, span: Span) -> Result<&mut FieldEntry<'a>> {
         field_of(&mut self.fields, key, span)
     }
+    /// Returns the regex that matches the fixed string `s` or the aliases.
+    fn literal_regex(&self, s: &str) -> String {
+        let mut regex = escape(s);
+        if !self.aliases.is_empty() {
+            for alias in &self.aliases {
+                regex.push('|');
+                regex.push_str(&escape(alias));
+            }
+            regex = format!("(?:{regex})");
+        }
+        self.with_case_flag(regex)
+    }
     fn set_capture(&mut self, vb: &VarBase, keys: &[FieldKey], span: Span) -> Result<String> {
         let field_key;
         let sub_keys;
@@ -170,7 +396,7 @@ impl<'a> ParserBuilder<'a> {
 
         if has_capture_empty {
             if let VarBase::Variant { variant, style, .. } = vb {
-                let value = style.apply(&variant.ident);
+                let value = style.apply(&variant.ident.to_string());
                 self.parse_format
                     .push_hir(to_hir_with_expand(&text, CAPTURE_NAME_EMPTY, &value));
                 return Ok(());
@@ -204,34 +430,52 @@ impl<'a> ParserBuilder<'a> {
                 DisplayFormatPart::Str(s) => self.push_str(s),
                 DisplayFormatPart::EscapedBeginBracket => self.push_str("{"),
                 DisplayFormatPart::EscapedEndBracket => self.push_str("}"),
-                DisplayFormatPart::Var { arg, .. } => {
+                DisplayFormatPart::Var { arg, format_spec } => {
+                    if arg == FIELD_NAME_ARG {
+                        self.push_str(&vb.styled_field_name(format.span)?);
+                        continue;
+                    }
                     let keys = FieldKey::from_str_deep(arg);
                     if let VarBase::Variant { variant, style, .. } = vb {
                         if keys.is_empty() {
-                            self.push_str(&style.apply(&variant.ident));
+                            self.push_str(&style.apply(&variant.ident.to_string()));
                             continue;
                         }
                     }
                     if keys.len() == 1 {
-                        self.push_field(vb, &keys[0], format.span)?;
+                        self.push_field(vb, &keys[0], format_spec, format.span)?;
                         continue;
                     }
+                    let mut with = with;
+                    let spec_with;
+                    if let VarBase::Field { key, .. } = vb {
+                        if keys.is_empty() && regex.is_none() {
+                            let e = self.field(key, format.span)?;
+                            if let Some(w) = e.apply_format_spec(format_spec, format.span)? {
+                                spec_with = w;
+                                with = Some(&spec_with);
+                            }
+                        }
+                    }
                     let c = self.set_capture(vb, &keys, format.span)?;
+                    if !keys.is_empty() || (regex.is_none() && with.is_none()) {
+                        self.parse_format.push_capture(c);
+                        continue;
+                    }
                     let mut f = format!("(?<{c}>(?s:.*?))");
-                    if keys.is_empty() {
-                        if let Some(regex) = regex {
-                            f = format!("(?<{c}>(?s:{regex}))");
-                        }
-                        if let Some(with_expr) = with {
-                            match vb {
-                                VarBase::Struct { .. } => {}
-                                VarBase::Variant { .. } => {}
-                                VarBase::Field { field, key, .. } => {
-                                    self.with.push(With::new(c, key, with_expr, &field.ty));
-                                }
-                                VarBase::FieldSome { key, ty } => {
-                                    self.with.push(With::new(c, key, with_expr, ty));
-                                }
+                    if let Some(regex) = regex {
+                        f = format!("(?<{c}>(?s:{regex}))");
+                    }
+                    if let Some(with_expr) = with {
+                        match vb {
+                            VarBase::Struct { .. } => {}
+                            VarBase::Variant { .. } => {}
+                            VarBase::Field { key, .. } => {
+                                let ty = self.field(key, format.span)?.parse_type();
+                                self.with.push(With::new(c, key, with_expr, ty));
+                            }
+                            VarBase::FieldSome { key, ty, .. } => {
+                                self.with.push(With::new(c, key, with_expr, ty));
                             }
                         }
                     }
@@ -244,21 +488,38 @@ impl<'a> ParserBuilder<'a> {
     fn push_str(&mut self, string: &str) {
         self.parse_format.push_str(string);
     }
-    fn push_field(&mut self, vb: &VarBase, key: &FieldKey, span: Span) -> Result<()> {
+    fn push_field(
+        &mut self,
+        vb: &VarBase,
+        key: &FieldKey,
+        format_spec: &str,
+        span: Span,
+    ) -> Result<()> {
         let e = self.field(key, span)?;
+        if e.hattrs.format.is_none() {
+            e.apply_format_spec(format_spec, span)?;
+        }
         let hattrs = e.hattrs.clone();
         let parent = vb;
         let field = e.source;
-        if e.hattrs.opt.value() {
+        if e.hattrs.opt.value() || e.hattrs.is_omittable() {
             let mut hirs = mem::take(&mut self.parse_format).into_hirs();
             self.push_attrs(&hattrs, &VarBase::Field { parent, key, field })?;
             let hirs_child = mem::take(&mut self.parse_format).into_hirs();
-            let hir = Hir::repetition(Repetition {
-                min: 0,
-                max: Some(1),
-                greedy: false,
-                sub: Box::new(Hir::concat(hirs_child)),
-            });
+            let hir = if hattrs.opt_none.is_empty() {
+                Hir::repetition(Repetition {
+                    min: 0,
+                    max: Some(1),
+                    greedy: false,
+                    sub: Box::new(Hir::concat(hirs_child)),
+                })
+            } else {
+                // `None` is preferred if the input is the string for `None`.
+                Hir::alternation(vec![
+                    Hir::literal(hattrs.opt_none.as_bytes()),
+                    Hir::concat(hirs_child),
+                ])
+            };
             hirs.push(hir);
             self.parse_format = ParseFormat::Hirs(hirs);
             Ok(())
@@ -291,28 +552,213 @@ impl<'a> ParserBuilder<'a> {
 
     pub fn build_from_str_body(&self, constructor: Path) -> Result<TokenStream> {
         let code = self.build_parse_code(constructor)?;
+        if self.kv.is_some() && (self.new_expr.is_none() || self.custom_error) {
+            return Ok(code);
+        }
         let crate_path = self.crate_path;
+        let e = if self.custom_error {
+            quote!(::core::convert::From::from(#crate_path::ParseError::new()))
+        } else {
+            quote!(#crate_path::ParseError::new())
+        };
         Ok(quote! {
             #code
-            ::core::result::Result::Err(#crate_path::ParseError::new())
+            ::core::result::Result::Err(#e)
         })
     }
     pub fn build_from_str_regex_body(&self) -> Result<TokenStream> {
+        if self.kv.is_some() || self.any_order.is_some() {
+            return Ok(quote! { "(?s:.*?)".into() });
+        }
         match &self.parse_format {
             ParseFormat::Hirs(hirs) => {
-                let expr = self.build_parser_init(hirs)?.expr;
+                let expr = self.build_parser_init(hirs, None)?.expr;
                 Ok(quote! { (#expr).re_str })
             }
+            ParseFormat::Parts(parts) => {
+                let s = self.with_case_flag(parts_to_regex_string(parts));
+                Ok(quote! { #s.into() })
+            }
             ParseFormat::String(s) => {
-                let s = escape(s);
+                let s = self.literal_regex(s);
                 Ok(quote! { #s.into() })
             }
         }
     }
 
-    pub fn build_parse_variant_code(&self, constructor: Path) -> Result<ParseVariantCode> {
+    /// Builds the code that returns the value parsed from the beginning of `s` and the rest of `s`, for `#[from_str(prefix)]`.
+    ///
+    /// If the beginning of `s` matches but the value cannot be constructed, the error is stored in `error` unless it already has one.
+    pub fn build_prefix_code(
+        &self,
+        constructor: Path,
+        generic: bool,
+        span: Span,
+    ) -> Result<TokenStream> {
+        let hirs = self.match_hirs("prefix", span)?;
+        let parser = self.build_parser_var(&hirs_with_start(&hirs), generic)?;
+        let code = self.build_construct_code(constructor)?;
+        let (err_ty, fallthrough) = self.build_closure_err_ty();
+        let input_len = self.build_input_len();
+        let fn_ident: Ident = format_ident!("parse_value");
+        Ok(quote! {
+            #parser
+            if let ::core::option::Option::Some(c) = p.re.captures(s) {
+                let rest = &s[c.get_match().end()..];
+                let #fn_ident = || -> ::core::result::Result<Self, #err_ty> {
+                    #input_len
+                    #code
+                    #fallthrough
+                };
+                match #fn_ident() {
+                    ::core::result::Result::Ok(value) => return ::core::result::Result::Ok((value, rest)),
+                    ::core::result::Result::Err(e) => {
+                        if error.is_none() {
+                            error = ::core::option::Option::Some(e);
+                        }
+                    }
+                }
+            }
+        })
+    }
+    /// Builds the code that sets `found` to the value found in `s` at or after `start`, for `#[from_str(find)]`.
+    ///
+    /// `found` is replaced only if the value is found before the value that `found` already has.
+    pub fn build_find_code(
+        &self,
+        constructor: Path,
+        generic: bool,
+        span: Span,
+    ) -> Result<TokenStream> {
+        let hirs = self.match_hirs("find", span)?;
+        let parser = self.build_parser_var(&hirs, generic)?;
+        let re = Regex::new(&to_regex_string(&hirs)).unwrap();
+        let mut names = HashMap::new();
+        for (index, name) in re.capture_names().enumerate() {
+            if let Some(name) = name {
+                names.insert(name, index);
+            }
+        }
+        let fields = self.fields.iter().filter_map(|(key, field)| {
+            let name = key.to_string();
+            let index = field.capture_index(&names)?;
+            Some(quote!((#name, #index)))
+        });
+        let code = self.build_construct_code(constructor)?;
+        let (err_ty, fallthrough) = self.build_closure_err_ty();
+        let input_len = self.build_input_len();
+        let crate_path = self.crate_path;
+        let fn_ident: Ident = format_ident!("parse_value");
+        Ok(quote! {
+            #parser
+            if let ::core::option::Option::Some(c) = p.re.captures_at(s, start) {
+                if found.as_ref().is_none_or(|f| c.get_match().start() < f.range().start) {
+                    let #fn_ident = || -> ::core::result::Result<Self, #err_ty> {
+                        #input_len
+                        #code
+                        #fallthrough
+                    };
+                    found = ::core::option::Option::Some(#crate_path::helpers::find_captures(s, &c, &[#(#fields),*], #fn_ident()));
+                }
+            }
+        })
+    }
+    /// Returns the hirs of the format, for the attribute `#[from_str(#attr)]` that matches the format with a regex.
+    fn match_hirs(&self, attr: &str, span: Span) -> Result<Vec<Hir>> {
+        if self.kv.is_some() {
+            bail!(
+                span,
+                "`#[from_str({attr})]` cannot be specified with `#[display(kv)]`."
+            );
+        }
+        if self.any_order.is_some() {
+            bail!(
+                span,
+                "`#[from_str({attr})]` cannot be specified with `#[from_str(any_order)]`."
+            );
+        }
+        Ok(match &self.parse_format {
+            ParseFormat::Hirs(hirs) => hirs.clone(),
+            ParseFormat::Parts(parts) => parts_to_hirs(parts),
+            ParseFormat::String(s) => {
+                let alts = [s]
+                    .into_iter()
+                    .chain(&self.aliases)
+                    .map(|s| Hir::literal(s.as_bytes()))
+                    .collect();
+                vec![Hir::alternation(alts)]
+            }
+        })
+    }
+    /// Builds the code that sets `p` to the parser of `hirs`.
+    fn build_parser_var(&self, hirs: &[Hir], generic: bool) -> Result<TokenStream> {
+        let crate_path = self.crate_path;
+        let expr = self.build_parser_init(hirs, None)?.expr;
+        // The parser cannot be shared by the instances of a generic type, since the regexes of the fields may depend on the parameters.
+        Ok(if generic {
+            quote! {
+                #[allow(clippy::trivial_regex)]
+                let p = &#expr;
+            }
+        } else {
+            quote! {
+                static PARSER: ::std::sync::OnceLock<#crate_path::helpers::Parser> = ::std::sync::OnceLock::new();
+                #[allow(clippy::trivial_regex)]
+                let p = PARSER.get_or_init(|| #expr);
+            }
+        })
+    }
+    fn build_input_len(&self) -> TokenStream {
+        if self.custom_error {
+            quote! {}
+        } else {
+            let input_len = input_len_ident();
+            quote! { let #input_len = s.len(); }
+        }
+    }
+
+    pub fn build_parse_variant_code(
+        &self,
+        constructor: Path,
+        variant_name: &str,
+    ) -> Result<ParseVariantCode> {
         match &self.parse_format {
-            ParseFormat::Hirs(_) => {
+            ParseFormat::Hirs(_) if self.custom_error && self.backtrack.is_some() => {
+                let code = self.build_construct_code(constructor)?;
+                let code = self.build_backtrack_code(
+                    code,
+                    quote! {
+                        match r {
+                            ::core::result::Result::Ok(value) => return ::core::result::Result::Ok(value),
+                            ::core::result::Result::Err(e) => {
+                                if error.is_none() {
+                                    error = ::core::option::Option::Some(e);
+                                }
+                            }
+                        }
+                    },
+                )?;
+                Ok(ParseVariantCode::Statement(code))
+            }
+            ParseFormat::Hirs(_) | ParseFormat::Parts(_) if self.custom_error => {
+                let fn_ident: Ident = format_ident!("parse_variant");
+                let code = self.build_construct_code(constructor)?;
+                let code = self.build_match_code(quote! {
+                    let #fn_ident = || -> ::core::result::Result<Self, Self::Err> {
+                        #code
+                    };
+                    match #fn_ident() {
+                        ::core::result::Result::Ok(value) => return ::core::result::Result::Ok(value),
+                        ::core::result::Result::Err(e) => {
+                            if error.is_none() {
+                                error = ::core::option::Option::Some(e);
+                            }
+                        }
+                    }
+                })?;
+                Ok(ParseVariantCode::Statement(code))
+            }
+            ParseFormat::Hirs(_) | ParseFormat::Parts(_) => {
                 let fn_ident: Ident = format_ident!("parse_variant");
                 let crate_path = self.crate_path;
                 let code = self.build_from_str_body(constructor)?;
@@ -320,15 +766,25 @@ impl<'a> ParserBuilder<'a> {
                     let #fn_ident = |s: &str| -> ::core::result::Result<Self, #crate_path::ParseError> {
                         #code
                     };
-                    if let ::core::result::Result::Ok(value) = #fn_ident(s) {
-                        return ::core::result::Result::Ok(value);
+                    match #fn_ident(s) {
+                        ::core::result::Result::Ok(value) => return ::core::result::Result::Ok(value),
+                        ::core::result::Result::Err(e) => {
+                            if error.is_none() && e.field().is_some() {
+                                error = ::core::option::Option::Some(e.with_variant(#variant_name));
+                            }
+                        }
                     }
                 };
                 Ok(ParseVariantCode::Statement(code))
             }
+            ParseFormat::String(_) if self.ignore_case => {
+                let code = self.build_construct_code(constructor)?;
+                Ok(ParseVariantCode::Statement(self.build_match_code(code)?))
+            }
             ParseFormat::String(s) => {
                 let code = self.build_construct_code(constructor)?;
-                let code = quote! { #s  => { #code }};
+                let aliases = &self.aliases;
+                let code = quote! { #s #(| #aliases)*  => { #code }};
                 Ok(ParseVariantCode::MatchArm(code))
             }
         }
@@ -346,8 +802,28 @@ impl<'a> ParserBuilder<'a> {
                     }
                 }
             }
+            ParseFormat::Parts(parts) => {
+                let captures = parts.iter().filter_map(|part| match part {
+                    FormatPart::Capture(name) => Some(name.as_str()),
+                    FormatPart::Str(_) => None,
+                });
+                for (index, name) in captures.enumerate() {
+                    names.entry(name).or_insert(index + 1);
+                }
+            }
             ParseFormat::String(_) => {}
         }
+        if let Some(kv) = &self.kv {
+            for (index, name) in kv.captures.iter().enumerate() {
+                names.insert(name, index + 1);
+            }
+        }
+        let capture_names: Vec<_> = (1..self.capture_next).map(capture_name).collect();
+        if self.any_order.is_some() {
+            for (index, name) in capture_names.iter().enumerate() {
+                names.insert(name, index + 1);
+            }
+        }
 
         let code = if let Some(new_expr) = &self.new_expr {
             let mut code = TokenStream::new();
@@ -357,11 +833,17 @@ impl<'a> ParserBuilder<'a> {
                 code.extend(quote! { let #var = #expr; });
             }
             let crate_path = self.crate_path;
-            code.extend(quote! {
-                if let ::core::result::Result::Ok(value) = #crate_path::IntoResult::into_result(#new_expr) {
-                    return ::core::result::Result::Ok(value);
-                }
-            });
+            if self.custom_error {
+                code.extend(quote! {
+                    return #crate_path::helpers::IntoResultWithError::<Self, Self::Err>::into_result_with_error(#new_expr);
+                });
+            } else {
+                code.extend(quote! {
+                    if let ::core::result::Result::Ok(value) = #crate_path::IntoResult::into_result(#new_expr) {
+                        return ::core::result::Result::Ok(value);
+                    }
+                });
+            }
             code
         } else if self.use_default {
             let mut setters = Vec::new();
@@ -397,8 +879,15 @@ impl<'a> ParserBuilder<'a> {
         };
         Ok(code)
     }
-    fn build_parser_init(&self, hirs: &[Hir]) -> Result<ParserInit> {
-        let regex = to_regex_string(hirs);
+    fn with_case_flag(&self, regex: String) -> String {
+        if self.ignore_case {
+            format!("(?i:{regex})")
+        } else {
+            regex
+        }
+    }
+    fn build_parser_init(&self, hirs: &[Hir], elements: Option<&[String]>) -> Result<ParserInit> {
+        let regex = self.with_case_flag(to_regex_string(hirs));
         let crate_path = self.crate_path;
         let mut with = Vec::new();
         let helpers = quote!( #crate_path::helpers );
@@ -422,37 +911,316 @@ impl<'a> ParserBuilder<'a> {
                 ::core::debug_assert_eq!(&p.ss[#index], &#helpers::to_regex::<#ty, _>(&#expr), #msg);
             });
         }
+        let expr = if let Some(elements) = elements {
+            quote!(#helpers::Parser::with_backtrack(#regex, &[#(#elements),*], &mut [#(#with,)*]))
+        } else {
+            quote!(#helpers::Parser::new(#regex, &mut [#(#with,)*]))
+        };
         Ok(ParserInit {
-            expr: quote!(#helpers::Parser::new(#regex, &mut [#(#with,)*])),
+            expr,
             debug_asserts,
         })
     }
     fn build_parse_code(&self, constructor: Path) -> Result<TokenStream> {
         let code = self.build_construct_code(constructor)?;
+        self.build_match_code(code)
+    }
+    fn build_match_code(&self, code: TokenStream) -> Result<TokenStream> {
+        let crate_path = self.crate_path;
+        let input_len = self.build_input_len();
+        if let Some(kv) = &self.kv {
+            let helpers = quote!(#crate_path::helpers);
+            let KvParser {
+                sep,
+                assign,
+                keys,
+                ignore_unknown_keys,
+                ..
+            } = kv;
+            let len = keys.len() + 1;
+            let keys = keys.iter().map(|(key, field)| quote!((#key, #field)));
+            let ignore_case = self.ignore_case;
+            return Ok(quote! {
+                let c = #helpers::match_kv::<#len>(s, &#helpers::KvFormat {
+                    sep: #sep,
+                    assign: #assign,
+                    keys: &[#(#keys),*],
+                    ignore_case: #ignore_case,
+                    ignore_unknown_keys: #ignore_unknown_keys,
+                })?;
+                #input_len
+                #code
+            });
+        }
+        if let Some(any_order) = &self.any_order {
+            let helpers = quote!(#crate_path::helpers);
+            let delimiter = &any_order.delimiter;
+            let len = self.capture_next;
+            let segments = any_order
+                .segments
+                .iter()
+                .map(|segment| self.build_segment_matcher(segment, len))
+                .collect::<Result<Vec<_>>>()?;
+            let segments_len = segments.len();
+            let (err_ty, fallthrough) = self.build_closure_err_ty();
+            return Ok(quote! {
+                if let ::core::option::Option::Some(r) = #helpers::match_any_order::<#len, #segments_len, _, _>(s, #delimiter, &[#(#segments),*], |c| -> ::core::result::Result<Self, #err_ty> {
+                    #input_len
+                    #code
+                    #fallthrough
+                }) {
+                    return r;
+                }
+            });
+        }
         Ok(match &self.parse_format {
+            ParseFormat::Parts(parts) => {
+                let helpers = quote!(#crate_path::helpers);
+                let mut len: usize = 1;
+                let parts = parts.iter().map(|part| match part {
+                    FormatPart::Str(s) => quote!(#helpers::FormatPart::Str(#s)),
+                    FormatPart::Capture(_) => {
+                        len += 1;
+                        quote!(#helpers::FormatPart::Capture)
+                    }
+                });
+                let parts = quote!(#(#parts),*);
+                let ignore_case = self.ignore_case;
+                quote! {
+                    if let ::core::option::Option::Some(c) = #helpers::match_parts::<#len>(s, &[#parts], #ignore_case) {
+                        #input_len
+                        #code
+                    }
+                }
+            }
             ParseFormat::Hirs(hirs) => {
+                if !cfg!(feature = "std") {
+                    bail!(
+                        self.span,
+                        "`#[from_str(regex = ...)]`, `#[from_str(regex_infer)]`, `#[display(with = ...)]`, `#[display(opt)]` and format specs such as `{{:x}}` and `{{:>8}}` require the `std` feature."
+                    );
+                }
+                if self.backtrack.is_some() {
+                    return self.build_backtrack_code(code, quote!(return r;));
+                }
                 let ParserInit {
                     expr,
                     debug_asserts,
-                } = self.build_parser_init(&hirs_with_start_end(hirs))?;
-                let crate_path = self.crate_path;
+                } = self.build_parser_init(&hirs_with_start_end(hirs), None)?;
                 quote! {
                     static PARSER: ::std::sync::OnceLock<#crate_path::helpers::Parser> = ::std::sync::OnceLock::new();
                     #[allow(clippy::trivial_regex)]
                     let p = PARSER.get_or_init(|| #expr);
                     #(#debug_asserts)*
                     if let ::core::option::Option::Some(c) = p.re.captures(&s) {
-                         #code
+                        #input_len
+                        #code
                     }
                 }
             }
-            ParseFormat::String(s) => quote! {
-                if s == #s {
-                    #code
+            ParseFormat::String(s) => {
+                let conds = [s].into_iter().chain(&self.aliases).map(|s| {
+                    if self.ignore_case {
+                        quote!(#crate_path::helpers::eq_ignore_case(s, #s))
+                    } else {
+                        quote!(s == #s)
+                    }
+                });
+                quote! {
+                    if #(#conds)||* {
+                        #code
+                    }
                 }
-            },
+            }
+        })
+    }
+    /// Builds the code that calls `code` with the captures of each split tried by `#[from_str(backtrack)]`.
+    ///
+    /// `code` returns the result of the split, and `on_result` handles `r`, the result of the first successful split or the error of the first split.
+    fn build_backtrack_code(
+        &self,
+        code: TokenStream,
+        on_result: TokenStream,
+    ) -> Result<TokenStream> {
+        let ParseFormat::Hirs(hirs) = &self.parse_format else {
+            unreachable!()
+        };
+        let Some(limit) = self.backtrack else {
+            unreachable!()
+        };
+        if !cfg!(feature = "std") {
+            bail!(
+                self.span,
+                "`#[from_str(backtrack)]` requires the `std` feature."
+            );
+        }
+        let crate_path = self.crate_path;
+        let elements = self.backtrack_elements(hirs);
+        let ParserInit {
+            expr,
+            debug_asserts,
+        } = self.build_parser_init(&hirs_with_start_end(hirs), Some(&elements))?;
+        let len = Regex::new(&to_regex_string(hirs)).unwrap().captures_len();
+        let input_len = input_len_ident();
+        let (err_ty, fallthrough) = self.build_closure_err_ty();
+        Ok(quote! {
+            static PARSER: ::std::sync::OnceLock<#crate_path::helpers::Parser> = ::std::sync::OnceLock::new();
+            #[allow(clippy::trivial_regex)]
+            let p = PARSER.get_or_init(|| #expr);
+            #(#debug_asserts)*
+            if let ::core::option::Option::Some(r) = p.backtrack::<#len, _, _>(s, #limit, |c| -> ::core::result::Result<Self, #err_ty> {
+                let #input_len = s.len();
+                #code
+                #fallthrough
+            }) {
+                #on_result
+            }
         })
     }
+    /// Returns the error type of the closure that constructs the value from the captures,
+    /// and the expression that the closure returns when the construct code falls through.
+    fn build_closure_err_ty(&self) -> (TokenStream, TokenStream) {
+        let crate_path = self.crate_path;
+        if self.custom_error {
+            (quote!(Self::Err), quote!())
+        } else if self.new_expr.is_some() {
+            (
+                quote!(#crate_path::ParseError),
+                quote!(::core::result::Result::Err(#crate_path::ParseError::new())),
+            )
+        } else {
+            (quote!(#crate_path::ParseError), quote!())
+        }
+    }
+    /// Returns the regexes of the elements that are matched one by one by `#[from_str(backtrack)]`.
+    ///
+    /// Each element is a part of the format with captures, or a run of parts without captures.
+    fn backtrack_elements(&self, hirs: &[Hir]) -> Vec<String> {
+        let mut elements = Vec::new();
+        let mut literals = Vec::new();
+        for hir in hirs {
+            if hir.properties().explicit_captures_len() == 0 {
+                literals.push(hir.clone());
+            } else {
+                if !literals.is_empty() {
+                    elements.push(mem::take(&mut literals));
+                }
+                elements.push(vec![hir.clone()]);
+            }
+        }
+        if !literals.is_empty() {
+            elements.push(literals);
+        }
+        elements
+            .iter()
+            .map(|hirs| self.with_case_flag(to_regex_string(&hirs_with_start_end(hirs))))
+            .collect()
+    }
+    /// Builds a closure that matches a segment of `#[from_str(any_order)]` and writes the ranges of the captures.
+    fn build_segment_matcher(&self, segment: &ParseFormat, len: usize) -> Result<TokenStream> {
+        let crate_path = self.crate_path;
+        let helpers = quote!(#crate_path::helpers);
+        let set_ranges = |names: &HashMap<&str, usize>| {
+            let mut names: Vec<_> = names.iter().collect();
+            names.sort();
+            let sets = names.into_iter().map(|(name, local)| {
+                let index = capture_index_of(name);
+                quote! {
+                    ranges[#index] = c.get(#local).map(|m| {
+                        let r = m.range();
+                        (offset + r.start, offset + r.end)
+                    });
+                }
+            });
+            quote!(#(#sets)*)
+        };
+        let ranges_ty = quote!(&mut [::core::option::Option<(usize, usize)>; #len]);
+        Ok(match segment {
+            ParseFormat::Parts(parts) => {
+                let mut names = HashMap::new();
+                let mut parts_len: usize = 1;
+                let mut parts_code = Vec::new();
+                for part in parts {
+                    parts_code.push(match part {
+                        FormatPart::Str(s) => quote!(#helpers::FormatPart::Str(#s)),
+                        FormatPart::Capture(name) => {
+                            names.entry(name.as_str()).or_insert(parts_len);
+                            parts_len += 1;
+                            quote!(#helpers::FormatPart::Capture)
+                        }
+                    });
+                }
+                let set_ranges = set_ranges(&names);
+                let ignore_case = self.ignore_case;
+                quote! {
+                    &|s: &str, offset: usize, ranges: #ranges_ty| {
+                        if let ::core::option::Option::Some(c) = #helpers::match_parts::<#parts_len>(s, &[#(#parts_code),*], #ignore_case) {
+                            #set_ranges
+                            true
+                        } else {
+                            false
+                        }
+                    }
+                }
+            }
+            ParseFormat::Hirs(hirs) => {
+                if !cfg!(feature = "std") {
+                    bail!(
+                        self.span,
+                        "`#[from_str(regex = ...)]`, `#[from_str(regex_infer)]`, `#[display(with = ...)]`, `#[display(opt)]` and format specs such as `{{:x}}` and `{{:>8}}` require the `std` feature."
+                    );
+                }
+                let re = Regex::new(&to_regex_string(hirs)).unwrap();
+                let mut names = HashMap::new();
+                for (index, name) in re.capture_names().enumerate() {
+                    if let Some(name) = name {
+                        names.insert(name, index);
+                    }
+                }
+                let set_ranges = set_ranges(&names);
+                let ParserInit {
+                    expr,
+                    debug_asserts,
+                } = self.build_parser_init(&hirs_with_start_end(hirs), None)?;
+                quote! {
+                    &|s: &str, offset: usize, ranges: #ranges_ty| {
+                        static PARSER: ::std::sync::OnceLock<#helpers::Parser> = ::std::sync::OnceLock::new();
+                        #[allow(clippy::trivial_regex)]
+                        let p = PARSER.get_or_init(|| #expr);
+                        #(#debug_asserts)*
+                        if let ::core::option::Option::Some(c) = p.re.captures(&s) {
+                            #set_ranges
+                            true
+                        } else {
+                            false
+                        }
+                    }
+                }
+            }
+            ParseFormat::String(lit) => {
+                let cond = if self.ignore_case {
+                    quote!(#helpers::eq_ignore_case(s, #lit))
+                } else {
+                    quote!(s == #lit)
+                };
+                quote! {
+                    &|s: &str, _: usize, _: #ranges_ty| #cond
+                }
+            }
+        })
+    }
+    /// Returns the fixed string of the format followed by the aliases, if the format is a fixed string.
+    pub fn literals(&self) -> Option<Vec<&str>> {
+        match &self.parse_format {
+            ParseFormat::String(s) if self.new_expr.is_none() => Some(
+                [s.as_str()]
+                    .into_iter()
+                    .chain(self.aliases.iter().map(String::as_str))
+                    .collect(),
+            ),
+            _ => None,
+        }
+    }
     pub fn build_regex_fmts_args(
         &self,
         fmts: &mut Vec<Option<String>>,
@@ -461,11 +1229,14 @@ impl<'a> ParserBuilder<'a> {
         match &self.parse_format {
             ParseFormat::Hirs(hirs) => {
                 fmts.push(None);
-                let expr = self.build_parser_init(hirs)?.expr;
+                let expr = self.build_parser_init(hirs, None)?.expr;
                 args.push(quote!((#expr).re_str));
             }
+            ParseFormat::Parts(parts) => {
+                fmts.push(Some(self.with_case_flag(parts_to_regex_string(parts))));
+            }
             ParseFormat::String(s) => {
-                fmts.push(Some(s.clone()));
+                fmts.push(Some(self.literal_regex(s)));
             }
         }
         Ok(())
@@ -477,7 +1248,10 @@ impl<'a> ParserBuilder<'a> {
         }
         for (index, field) in self.fields.values().enumerate() {
             let mut bounds = bounds.child(field.hattrs.bound_from_str_resolved());
-            if bounds.can_extend && field.capture.is_some() && field.hattrs.with.is_none() {
+            if bounds.can_extend
+                && field.capture.is_some()
+                && (field.hattrs.with.is_none() || field.with_from_str || field.with_inferred)
+            {
                 let mut ty = &field.source.ty;
                 if field.hattrs.opt.value() {
                     if let Some(opt_ty) = get_option_element(ty) {
@@ -496,21 +1270,147 @@ impl<'a> ParserBuilder<'a> {
     }
 }
 impl<'a> FieldEntry<'a> {
-    fn new(source: &'a Field, regex_infer: bool, crate_path: &'a Path) -> Result<Self> {
+    fn new(
+        source: &'a Field,
+        regex_infer: bool,
+        strict: bool,
+        custom_error: bool,
+        crate_path: &'a Path,
+    ) -> Result<Self> {
         let mut hattrs = HelperAttributes::from(&source.attrs, true)?;
+        if let Some(error) = &hattrs.error {
+            bail!(
+                error.span(),
+                "`#[from_str(error = ...)]` cannot be specified for field."
+            );
+        }
+        if let Some(span) = hattrs.ignore_case.span {
+            bail!(
+                span,
+                "`#[from_str(ignore_case)]` cannot be specified for field."
+            );
+        }
+        if let Some(alias) = hattrs.aliases.first() {
+            bail!(
+                alias.span(),
+                "`#[from_str(alias = ...)]` cannot be specified for field."
+            );
+        }
+        if let Some(span) = hattrs.other.span {
+            bail!(span, "`#[from_str(other)]` cannot be specified for field.");
+        }
+        hattrs.check_no_kv()?;
+        hattrs.check_no_any_order()?;
+        if let Some(span) = hattrs.backtrack_span() {
+            bail!(
+                span,
+                "`#[from_str(backtrack)]` cannot be specified for field."
+            );
+        }
+        if let Some(span) = hattrs.prefix.span {
+            bail!(span, "`#[from_str(prefix)]` cannot be specified for field.");
+        }
+        if let Some(span) = hattrs.find.span {
+            bail!(span, "`#[from_str(find)]` cannot be specified for field.");
+        }
+        hattrs.check_omit()?;
+        let mut with_inferred = false;
         if (regex_infer || hattrs.regex_infer) && hattrs.with.is_none() {
             hattrs.with = Some(parse_quote!(#crate_path::helpers::RegexInfer));
+            with_inferred = true;
         };
         let use_default = hattrs.default_self.is_some();
+        let strict = strict || hattrs.strict;
         Ok(Self {
             hattrs,
             deep_captures: BTreeMap::new(),
             capture: None,
             use_default,
+            with_inferred,
+            with_from_str: false,
+            strict,
+            strict_format: None,
+            kv: false,
             source,
+            custom_error,
             crate_path,
         })
     }
+
+    /// Returns the type parsed from the capture. (The inner type for `#[display(opt)]`.)
+    fn parse_type(&self) -> &'a Type {
+        let ty = &self.source.ty;
+        if self.hattrs.opt.value() {
+            if let Some(inner_ty) = get_option_element(ty) {
+                return inner_ty;
+            }
+        }
+        ty
+    }
+
+    /// Uses the parser corresponding to the format spec (e.g. `{:x}`, `{:>8}`) if no other parser is specified.
+    fn apply_format_spec(&mut self, format_spec_str: &str, span: Span) -> Result<Option<Expr>> {
+        let format_spec = FormatSpec::parse_with_span(format_spec_str, span)?;
+        let ty = self.parse_type();
+        let crate_path = self.crate_path;
+        let is_integer = is_type_of(ty, INTEGER_TYPES);
+        let is_number = is_integer || is_type_of(ty, FLOAT_TYPES);
+        let has_user_with = self.hattrs.with.is_some() && !self.with_inferred;
+        if self.strict && is_number && !has_user_with && self.strict_format.is_none() {
+            let is_value =
+                |arg: &Option<SubArg<usize>>| matches!(arg, None | Some(SubArg::Value(_)));
+            if is_value(&format_spec.width) && is_value(&format_spec.precision) {
+                self.strict_format = Some(format!("{{:{format_spec_str}}}"));
+            }
+        }
+        if self.hattrs.regex.is_some() || has_user_with {
+            return Ok(None);
+        }
+        let radix: Option<u32> = match format_spec.format_type {
+            FormatType::Binary => Some(2),
+            FormatType::Octal => Some(8),
+            FormatType::LowerHex | FormatType::UpperHex => Some(16),
+            _ => None,
+        };
+        let mut with: Option<Expr> = None;
+        if let (true, Some(radix)) = (is_integer, radix) {
+            let alternate = format_spec.is_alternate;
+            with = Some(parse_quote!(#crate_path::helpers::Radix::new(#radix, #alternate)));
+        }
+        if let Some(width) = &format_spec.width {
+            let inner = if let Some(with) = with.take().or_else(|| self.hattrs.with.clone()) {
+                with
+            } else {
+                self.with_from_str = true;
+                parse_quote!(#crate_path::helpers::ByFromStr)
+            };
+            let fill = format_spec.fill.unwrap_or(' ');
+            let align = match format_spec.align {
+                Some(Align::Left) => quote!(Left),
+                Some(Align::Right) => quote!(Right),
+                Some(Align::Center) => quote!(Center),
+                None if is_number => quote!(Right),
+                None => quote!(Left),
+            };
+            let width = match width {
+                SubArg::Value(width) if is_number || is_type_of(ty, PADDED_TYPES) => {
+                    quote!(::core::option::Option::Some(#width))
+                }
+                _ => quote!(::core::option::Option::None),
+            };
+            with = Some(parse_quote!(#crate_path::helpers::Padding::new(
+                #inner,
+                #fill,
+                #crate_path::helpers::Align::#align,
+                #width
+            )));
+        }
+        if let Some(with) = &with {
+            self.hattrs.with = Some(with.clone());
+            self.with_inferred = false;
+        }
+        Ok(with)
+    }
     #[allow(clippy::collapsible_else_if)]
     fn set_capture(&mut self, keys: &[FieldKey], capture_next: &mut usize) -> String {
         let idx = if keys.is_empty() {
@@ -543,12 +1443,34 @@ impl<'a> FieldEntry<'a> {
         key: &FieldKey,
     ) -> Result<Option<TokenStream>> {
         if let Some(capture_index) = self.capture_index(names) {
-            Ok(Some(build_parse_capture_expr(
-                &key.to_string(),
+            let field_name = key.to_string();
+            let mut expr = build_parse_capture_expr(
+                &field_name,
                 capture_index,
-                Some(self),
+                CaptureTarget::Field(self),
+                self.custom_error,
                 self.crate_path,
-            )?))
+            )?;
+            if (self.kv && !self.hattrs.opt.value()) || self.hattrs.is_omittable() {
+                let crate_path = self.crate_path;
+                let missing = if self.use_default || self.hattrs.is_omittable() {
+                    quote! { ::core::default::Default::default() }
+                } else {
+                    quote! {
+                        ::core::result::Result::Err(
+                            #crate_path::ParseError::with_message("missing key.").with_field(#field_name),
+                        )?
+                    }
+                };
+                expr = quote! {
+                    if c.get(#capture_index).is_some() {
+                        #expr
+                    } else {
+                        #missing
+                    }
+                };
+            }
+            Ok(Some(expr))
         } else if self.use_default {
             Ok(Some(quote! { ::core::default::Default::default() }))
         } else {
@@ -565,15 +1487,24 @@ impl<'a> FieldEntry<'a> {
         let mut setters = Vec::new();
         if include_self {
             if let Some(expr) = self.build_expr(names, key)? {
-                setters.push(quote! { #left_expr = #expr; });
+                let setter = quote! { #left_expr = #expr; };
+                setters.push(match self.capture_index(names) {
+                    Some(capture_index) if self.kv => quote! {
+                        if c.get(#capture_index).is_some() {
+                            #setter
+                        }
+                    },
+                    _ => setter,
+                });
             }
         }
         for (keys, idx) in &self.deep_captures {
-            let field_name = key.to_string() + &join(keys, ".");
+            let field_name = format!("{key}.{}", join(keys, "."));
             let expr = build_parse_capture_expr(
                 &field_name,
                 capture_index(*idx, names),
-                None,
+                CaptureTarget::Place(quote!(#left_expr #(.#keys)*)),
+                self.custom_error,
                 self.crate_path,
             )?;
             setters.push(quote! { #left_expr #(.#keys)* = #expr; });
@@ -612,6 +1543,7 @@ pub enum ParseVariantCode {
 
 enum ParseFormat {
     Hirs(Vec<Hir>),
+    Parts(Vec<FormatPart>),
     String(String),
 }
 impl ParseFormat {
@@ -621,6 +1553,7 @@ impl ParseFormat {
     fn as_hirs(&mut self) -> &mut Vec<Hir> {
         match self {
             Self::Hirs(_) => {}
+            Self::Parts(parts) => *self = Self::Hirs(parts_to_hirs(parts)),
             Self::String(s) => {
                 let mut hirs = vec![];
                 push_str(&mut hirs, s);
@@ -637,19 +1570,70 @@ impl ParseFormat {
         self.as_hirs();
         match self {
             Self::Hirs(hirs) => hirs,
-            Self::String(_) => unreachable!(),
+            Self::Parts(_) | Self::String(_) => unreachable!(),
         }
     }
 
     fn push_str(&mut self, string: &str) {
         match self {
             Self::Hirs(hirs) => push_str(hirs, string),
+            Self::Parts(parts) => {
+                if let Some(FormatPart::Str(s)) = parts.last_mut() {
+                    s.push_str(string);
+                } else {
+                    parts.push(FormatPart::Str(string.into()));
+                }
+            }
             Self::String(s) => s.push_str(string),
         }
     }
     fn push_hir(&mut self, hir: Hir) {
         self.as_hirs().push(hir);
     }
+    fn push_capture(&mut self, name: String) {
+        match self {
+            Self::Hirs(hirs) => hirs.push(capture_hir(&name)),
+            Self::Parts(parts) => parts.push(FormatPart::Capture(name)),
+            Self::String(s) => {
+                let mut parts = Vec::new();
+                if !s.is_empty() {
+                    parts.push(FormatPart::Str(mem::take(s)));
+                }
+                parts.push(FormatPart::Capture(name));
+                *self = Self::Parts(parts);
+            }
+        }
+    }
+}
+
+/// A part of a format that can be parsed without regex.
+enum FormatPart {
+    Str(String),
+    Capture(String),
+}
+
+fn capture_hir(name: &str) -> Hir {
+    to_hir(&format!("(?<{name}>(?s:.*?))"))
+}
+fn parts_to_regex_string(parts: &[FormatPart]) -> String {
+    let hirs: Vec<_> = parts
+        .iter()
+        .map(|part| match part {
+            FormatPart::Str(s) => Hir::literal(s.as_bytes()),
+            FormatPart::Capture(_) => to_hir("(?s:.*?)"),
+        })
+        .collect();
+    to_regex_string(&hirs)
+}
+fn parts_to_hirs(parts: &[FormatPart]) -> Vec<Hir> {
+    let mut hirs = Vec::new();
+    for part in parts {
+        match part {
+            FormatPart::Str(s) => push_str(&mut hirs, s),
+            FormatPart::Capture(name) => hirs.push(capture_hir(name)),
+        }
+    }
+    hirs
 }
 impl Default for ParseFormat {
     fn default() -> Self {
@@ -662,46 +1646,115 @@ struct ParserInit {
     debug_asserts: Vec<TokenStream>,
 }
 
+enum CaptureTarget<'a> {
+    Field(&'a FieldEntry<'a>),
+    Place(TokenStream),
+}
+
 fn build_parse_capture_expr(
     field_name: &str,
     capture_index: usize,
-    field: Option<&FieldEntry>,
+    target: CaptureTarget,
+    custom_error: bool,
     crate_path: &Path,
 ) -> Result<TokenStream> {
-    let msg = format!("field `{field_name}` parse failed.");
-    let e = if let Some(field) = field {
-        if field.hattrs.opt.value() {
-            let e = str_expr_to_parse_capture_expr(quote!(s), field, crate_path);
-            quote! {
-                c.get(#capture_index).map(|m| m.as_str()).map(|s| #e).transpose()
+    let mut strict_format = None;
+    let mut is_opt = false;
+    let e = match target {
+        CaptureTarget::Field(field) => {
+            strict_format = field.strict_format.as_deref();
+            is_opt = field.hattrs.opt.value();
+            if field.hattrs.opt.value() {
+                let e = str_expr_to_parse_capture_expr(quote!(s), field, crate_path);
+                let none_value = &field.hattrs.opt_none;
+                // In `#[display(kv)]`, the value is the string for `None` instead of the alternative in the regex.
+                let filter = if field.kv && !none_value.is_empty() {
+                    quote!(.filter(|s| *s != #none_value))
+                } else {
+                    quote!()
+                };
+                quote! {
+                    c.get(#capture_index).map(|m| m.as_str())#filter.map(|s| #e).transpose()
+                }
+            } else {
+                str_expr_to_parse_capture_expr(
+                    quote!(c.get(#capture_index).map_or("", |m| m.as_str())),
+                    field,
+                    crate_path,
+                )
             }
-        } else {
-            str_expr_to_parse_capture_expr(
-                quote!(c.get(#capture_index).map_or("", |m| m.as_str())),
-                field,
-                crate_path,
-            )
         }
+        CaptureTarget::Place(place) => quote! {
+            #crate_path::helpers::parse_as(&#place, c.get(#capture_index).map_or("", |m| m.as_str()))
+        },
+    };
+    let input_len = input_len_ident();
+    let with_span = if custom_error {
+        quote!()
+    } else {
+        quote!(.with_span(c.get(#capture_index).map_or(0..0, |m| m.range()), #input_len))
+    };
+    let e = if custom_error {
+        quote!(#e?)
     } else {
-        quote!(c.get(#capture_index).map_or("", |m| m.as_str()).parse())
+        quote! {
+            #e.map_err(|e| {
+                #[allow(unused_imports)]
+                use #crate_path::helpers::{AttachNoSource as _, AttachSource as _};
+                #crate_path::helpers::SourceError(e).attach_to(
+                    #crate_path::ParseError::new()
+                        .with_field(#field_name)
+                        #with_span,
+                )
+            })?
+        }
     };
-    Ok(quote! {
-        #e.map_err(|e| #crate_path::ParseError::with_message(#msg))?
-    })
+    if let Some(format) = strict_format {
+        let value = Ident::new("value", Span::mixed_site());
+        let s = quote!(c.get(#capture_index).map_or("", |m| m.as_str()));
+        let check = if is_opt {
+            quote! {
+                ::core::option::Option::map_or(#value.as_ref(), true, |#value| {
+                    #crate_path::helpers::is_displayed_as(#s, ::core::format_args!(#format, #value))
+                })
+            }
+        } else {
+            quote!(#crate_path::helpers::is_displayed_as(#s, ::core::format_args!(#format, &#value)))
+        };
+        let message = format!("not formatted as `{format}`.");
+        return Ok(quote! {{
+            let #value = #e;
+            if !#check {
+                ::core::result::Result::Err(
+                    #crate_path::ParseError::with_message(#message)
+                        .with_field(#field_name)
+                        #with_span,
+                )?;
+            }
+            #value
+        }});
+    }
+    Ok(e)
+}
+
+// Uses mixed-site hygiene so that variables bound by `#[from_str(new = ...)]` cannot shadow it.
+fn input_len_ident() -> Ident {
+    Ident::new("input_len", Span::mixed_site())
 }
 fn str_expr_to_parse_capture_expr(
     str_expr: TokenStream,
     field: &FieldEntry,
     crate_path: &Path,
 ) -> TokenStream {
+    // The type is written explicitly so that the error type is known when attaching it as the source.
+    let ty = field.parse_type();
     if let Some(with) = &field.hattrs.with {
-        let ty = &field.source.ty;
         let expr = quote! {
             #crate_path::helpers::parse_with::<#ty, _>(#with, #str_expr)
         };
         set_span(expr, with.span())
     } else {
-        quote!(#str_expr.parse())
+        quote!(<#ty as ::core::str::FromStr>::from_str(#str_expr))
     }
 }
 
@@ -712,6 +1765,9 @@ fn capture_name(idx: usize) -> String {
 fn capture_index(idx: usize, names: &HashMap<&str, usize>) -> usize {
     names[capture_name(idx).as_str()]
 }
+fn capture_index_of(name: &str) -> usize {
+    name.strip_prefix("value_").unwrap().parse().unwrap()
+}
 
 struct FieldEntry<'a> {
     hattrs: HelperAttributes,
@@ -719,6 +1775,13 @@ struct FieldEntry<'a> {
     source: &'a Field,
     capture: Option<usize>,
     use_default: bool,
+    with_inferred: bool,
+    with_from_str: bool,
+    strict: bool,
+    strict_format: Option<String>,
+    /// `true` if the field is parsed from a key-value pair of `#[display(kv)]`, which may be missing.
+    kv: bool,
+    custom_error: bool,
     crate_path: &'a Path,
 }
 
@@ -28,6 +28,11 @@ fn to_ast(s: &str) -> Ast {
 pub fn push_str(hirs: &mut Vec<Hir>, s: &str) {
     hirs.push(Hir::literal(s.as_bytes()));
 }
+pub fn hirs_with_start(hirs: &[Hir]) -> Vec<Hir> {
+    let mut hs = vec![Hir::look(regex_syntax::hir::Look::Start)];
+    hs.extend(hirs.iter().cloned());
+    hs
+}
 pub fn hirs_with_start_end(hirs: &[Hir]) -> Vec<Hir> {
     let mut hs = vec![Hir::look(regex_syntax::hir::Look::Start)];
     hs.extend(hirs.iter().cloned());
@@ -0,0 +1,122 @@
+use crate::{DisplayFormat, DisplayFormatPart, derive_from_str_for_struct};
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::{
+    Data, DeriveInput, Expr, Ident, LitStr, Result, Token, Type,
+    ext::IdentExt,
+    parse::{Parse, ParseStream},
+    parse_quote, parse_str,
+};
+
+/// The input of `scan!(input, "format")`.
+pub struct ScanInput {
+    input: Expr,
+    format: LitStr,
+}
+impl Parse for ScanInput {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let expr = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let format = input.parse()?;
+        input.parse::<Option<Token![,]>>()?;
+        Ok(Self {
+            input: expr,
+            format,
+        })
+    }
+}
+
+/// A placeholder of the format of `scan!`.
+struct ScanArg {
+    name: Option<String>,
+    ty: Option<Type>,
+}
+
+pub fn expand_scan(input: ScanInput) -> Result<TokenStream> {
+    let span = input.format.span();
+    let format = DisplayFormat::parse_lit_str(&input.format)?;
+    let mut args: Vec<ScanArg> = Vec::new();
+    let mut struct_format = String::new();
+    for part in &format.parts {
+        match part {
+            DisplayFormatPart::Str(s) => struct_format.push_str(&crate::escape_fmt(s)),
+            DisplayFormatPart::EscapedBeginBracket => struct_format.push_str("{{"),
+            DisplayFormatPart::EscapedEndBracket => struct_format.push_str("}}"),
+            DisplayFormatPart::Var { arg, format_spec } => {
+                let index = push_arg(&mut args, arg, format_spec, span)?;
+                struct_format.push_str(&format!("{{{index}}}"));
+            }
+        }
+    }
+    let struct_format = LitStr::new(&struct_format, span);
+    let ident = Ident::new("ParseDisplayScan", Span::mixed_site());
+    let params: Vec<_> = (0..args.len()).map(|i| format_ident!("T{i}")).collect();
+    let derive_input: DeriveInput = parse_quote! {
+        #[display(#struct_format)]
+        struct #ident<#(#params),*>(#(#params),*);
+    };
+    let Data::Struct(data) = &derive_input.data else {
+        unreachable!()
+    };
+    let impls = derive_from_str_for_struct(&derive_input, data)?;
+    let tys = args.iter().map(|arg| match &arg.ty {
+        Some(ty) => quote!(#ty),
+        None => quote!(_),
+    });
+    let vars: Vec<_> = (0..args.len()).map(|i| format_ident!("v{i}")).collect();
+    let value = if vars.len() == 1 {
+        quote!(#(#vars)*)
+    } else {
+        quote!((#(#vars,)*))
+    };
+    let input = &input.input;
+    Ok(quote! {
+        match ::core::convert::AsRef::<str>::as_ref(&#input) {
+            s => {
+                struct #ident<#(#params),*>(#(#params),*);
+                #impls
+                ::core::result::Result::map(
+                    <#ident<#(#tys),*> as ::core::str::FromStr>::from_str(s),
+                    |#ident(#(#vars),*)| #value,
+                )
+            }
+        }
+    })
+}
+
+/// Adds the placeholder `{arg:format_spec}` to `args` and returns its index.
+///
+/// Placeholders with the same name share the same index.
+fn push_arg(args: &mut Vec<ScanArg>, arg: &str, format_spec: &str, span: Span) -> Result<usize> {
+    let name = if arg.is_empty() {
+        None
+    } else if parse_str::<Ident>(arg).is_ok_and(|ident| ident.unraw() != "_") {
+        Some(arg.to_string())
+    } else {
+        bail!(
+            span,
+            "invalid placeholder `{{{arg}}}`. expected `{{}}` or `{{name}}`."
+        );
+    };
+    let ty = if format_spec.is_empty() {
+        None
+    } else {
+        match parse_str::<Type>(format_spec) {
+            Ok(ty) => Some(ty),
+            Err(_) => bail!(span, "invalid type `{format_spec}`."),
+        }
+    };
+    if name.is_some() {
+        if let Some(index) = args.iter().position(|a| a.name == name) {
+            if ty.is_some() {
+                if args[index].ty.is_some() {
+                    bail!(span, "the type of `{arg}` is specified more than once.");
+                }
+                args[index].ty = ty;
+            }
+            return Ok(index);
+        }
+    }
+    args.push(ScanArg { name, ty });
+    Ok(args.len() - 1)
+}
@@ -3,7 +3,7 @@ use quote::{ToTokens, quote, quote_spanned};
 use std::collections::HashSet;
 use syn::{
     DeriveInput, GenericParam, Generics, Ident, LitStr, Path, PathArguments, PathSegment, Result,
-    Token, Type, WherePredicate,
+    Token, Type, WherePredicate, bracketed,
     ext::IdentExt,
     parse::{Parse, ParseStream, discouraged::Speculative},
     parse_str, parse2,
@@ -137,6 +137,24 @@ impl<T> ArgsOf<T> {
     }
 }
 
+/// `[arg, arg, ...]`
+pub struct ArrayOf<T>(Vec<T>);
+
+impl<T: Parse> Parse for ArrayOf<T> {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let content;
+        bracketed!(content in input);
+        let args = Punctuated::<T, Token![,]>::parse_terminated(&content)?;
+        Ok(Self(args.into_iter().collect()))
+    }
+}
+
+impl<T> ArrayOf<T> {
+    pub fn into_iter(self) -> impl Iterator<Item = T> {
+        self.0.into_iter()
+    }
+}
+
 pub fn impl_trait(
     input: &DeriveInput,
     trait_path: &Path,
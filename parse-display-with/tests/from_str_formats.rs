@@ -39,6 +39,16 @@ fn with_and_default_bound() {
     assert_from_str("10, 20, 30", X(vec![10, 20, 30]));
 }
 
+#[test]
+fn delimiter_opt_empty() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display("[{0}]")]
+    struct X(#[display(":{}", with = delimiter(","), opt_empty)] Vec<u32>);
+
+    assert_from_str("[:10,20]", X(vec![10, 20]));
+    assert_from_str("[]", X(vec![]));
+}
+
 fn assert_from_str<T: FromStr + Debug + PartialEq>(s: &str, value: T)
 where
     <T as FromStr>::Err: Display,
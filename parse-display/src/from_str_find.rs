@@ -0,0 +1,114 @@
+use core::{marker::PhantomData, ops::Range, str::FromStr};
+
+/// A trait for finding values in a larger string.
+///
+/// When using [`#[derive(FromStr)]`](derive@crate::FromStr) with the [`#[from_str(find)]`](derive@crate::Display#from_strfind) attribute,
+/// this trait is also implemented.
+pub trait FromStrFind: FromStr {
+    /// Returns the first value found in `text` at or after the byte offset `start`, with the captures of its fields.
+    ///
+    /// The text before `start` is still used to match the beginning of the value, such as `\b`.
+    fn captures_at(text: &str, start: usize) -> Option<FindCaptures<'_, Self>>;
+
+    /// Returns the first value found in `text`, with the captures of its fields.
+    fn captures(text: &str) -> Option<FindCaptures<'_, Self>> {
+        Self::captures_at(text, 0)
+    }
+
+    /// Returns an iterator over the values found in `text`.
+    ///
+    /// Each item is the byte range of the match in `text` and the result of parsing the matched string.
+    fn find_iter(text: &str) -> FindIter<'_, Self> {
+        FindIter {
+            text,
+            pos: 0,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Returns the first value found in `text`.
+    fn find(text: &str) -> Option<FindItem<Self>> {
+        Self::captures(text).map(FindCaptures::into_item)
+    }
+}
+
+/// A value found by [`FromStrFind`], with the byte range of the match.
+pub type FindItem<T> = (Range<usize>, Result<T, <T as FromStr>::Err>);
+
+/// A value found by [`FromStrFind`], with the byte ranges of the match and its fields.
+pub struct FindCaptures<'a, T: FromStr> {
+    pub(crate) text: &'a str,
+    pub(crate) range: Range<usize>,
+    pub(crate) fields: Vec<(&'static str, Option<Range<usize>>)>,
+    pub(crate) value: Result<T, T::Err>,
+}
+
+impl<'a, T: FromStr> FindCaptures<'a, T> {
+    /// Returns the byte range of the match in the text.
+    pub fn range(&self) -> Range<usize> {
+        self.range.clone()
+    }
+
+    /// Returns the matched string.
+    pub fn as_str(&self) -> &'a str {
+        &self.text[self.range()]
+    }
+
+    /// Returns the byte range in the text of the string matched by the field `name`.
+    ///
+    /// Returns `None` if the field does not exist or did not participate in the match.
+    /// Tuple fields are named by their index, such as `"0"`.
+    pub fn field_range(&self, name: &str) -> Option<Range<usize>> {
+        self.fields
+            .iter()
+            .find(|(n, _)| *n == name)
+            .and_then(|(_, range)| range.clone())
+    }
+
+    /// Returns the string matched by the field `name`.
+    ///
+    /// Returns `None` if the field does not exist or did not participate in the match.
+    pub fn field(&self, name: &str) -> Option<&'a str> {
+        self.field_range(name).map(|range| &self.text[range])
+    }
+
+    /// Returns the result of parsing the matched string.
+    pub fn value(&self) -> &Result<T, T::Err> {
+        &self.value
+    }
+
+    /// Returns the result of parsing the matched string, consuming `self`.
+    pub fn into_value(self) -> Result<T, T::Err> {
+        self.value
+    }
+
+    /// Returns the byte range of the match and the result of parsing it, consuming `self`.
+    pub fn into_item(self) -> FindItem<T> {
+        (self.range, self.value)
+    }
+}
+
+/// An iterator over the values found in a string, created by [`FromStrFind::find_iter`].
+pub struct FindIter<'a, T> {
+    text: &'a str,
+    pos: usize,
+    _phantom: PhantomData<fn() -> T>,
+}
+
+impl<T: FromStrFind> Iterator for FindIter<'_, T> {
+    type Item = FindItem<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.pos > self.text.len() {
+            return None;
+        }
+        let c = T::captures_at(self.text, self.pos)?;
+        let end = c.range.end;
+        self.pos = if c.range.is_empty() {
+            end + self.text[end..].chars().next().map_or(1, char::len_utf8)
+        } else {
+            end
+        };
+        Some(c.into_item())
+    }
+}
@@ -0,0 +1,113 @@
+use core::{num::NonZero, str::FromStr};
+
+/// A trait for parsing a value from the beginning of a string.
+///
+/// When using [`#[derive(FromStr)]`](derive@crate::FromStr) with the [`#[from_str(prefix)]`](derive@crate::Display#from_strprefix) attribute,
+/// this trait is also implemented.
+pub trait FromStrPrefix: FromStr {
+    /// Parses a value from the beginning of `s`, and returns the value and the rest of `s`.
+    fn parse_prefix(s: &str) -> Result<(Self, &str), Self::Err>;
+}
+
+/// Parses the first `len` bytes of `s`.
+///
+/// If no valid prefix is found, `len` is `0` and the error of parsing an empty string is returned.
+fn parse_len<T: FromStr>(s: &str, len: usize) -> Result<(T, &str), T::Err> {
+    Ok((s[..len].parse()?, &s[len..]))
+}
+
+fn digits_len(s: &[u8]) -> usize {
+    s.iter().take_while(|b| b.is_ascii_digit()).count()
+}
+fn sign_len(s: &[u8], allow_minus: bool) -> usize {
+    match s.first() {
+        Some(b'+') => 1,
+        Some(b'-') if allow_minus => 1,
+        _ => 0,
+    }
+}
+fn int_len(s: &str, allow_minus: bool) -> usize {
+    let s = s.as_bytes();
+    let sign = sign_len(s, allow_minus);
+    let digits = digits_len(&s[sign..]);
+    if digits == 0 { 0 } else { sign + digits }
+}
+fn float_len(s: &str) -> usize {
+    let b = s.as_bytes();
+    let sign = sign_len(b, true);
+    let rest = &s[sign..];
+    for word in ["infinity", "inf", "nan"] {
+        if rest
+            .get(..word.len())
+            .is_some_and(|p| p.eq_ignore_ascii_case(word))
+        {
+            return sign + word.len();
+        }
+    }
+    let b = &b[sign..];
+    let int = digits_len(b);
+    let mut len = int;
+    if b.get(len) == Some(&b'.') {
+        let frac = digits_len(&b[len + 1..]);
+        if int == 0 && frac == 0 {
+            return 0;
+        }
+        len += 1 + frac;
+    } else if int == 0 {
+        return 0;
+    }
+    if let Some(b'e' | b'E') = b.get(len) {
+        let exp_sign = sign_len(&b[len + 1..], true);
+        let exp = digits_len(&b[len + 1 + exp_sign..]);
+        if exp != 0 {
+            len += 1 + exp_sign + exp;
+        }
+    }
+    sign + len
+}
+
+macro_rules! impl_from_str_prefix_for_int {
+    ($allow_minus:expr, $($t:ty),*) => {
+        $(
+            impl FromStrPrefix for $t {
+                fn parse_prefix(s: &str) -> Result<(Self, &str), Self::Err> {
+                    parse_len(s, int_len(s, $allow_minus))
+                }
+            }
+            impl FromStrPrefix for NonZero<$t> {
+                fn parse_prefix(s: &str) -> Result<(Self, &str), Self::Err> {
+                    parse_len(s, int_len(s, $allow_minus))
+                }
+            }
+        )*
+    };
+}
+impl_from_str_prefix_for_int!(false, u8, u16, u32, u64, u128, usize);
+impl_from_str_prefix_for_int!(true, i8, i16, i32, i64, i128, isize);
+
+impl FromStrPrefix for f32 {
+    fn parse_prefix(s: &str) -> Result<(Self, &str), Self::Err> {
+        parse_len(s, float_len(s))
+    }
+}
+impl FromStrPrefix for f64 {
+    fn parse_prefix(s: &str) -> Result<(Self, &str), Self::Err> {
+        parse_len(s, float_len(s))
+    }
+}
+
+impl FromStrPrefix for bool {
+    fn parse_prefix(s: &str) -> Result<(Self, &str), Self::Err> {
+        let len = ["true", "false"]
+            .into_iter()
+            .find(|p| s.starts_with(p))
+            .map_or(0, str::len);
+        parse_len(s, len)
+    }
+}
+
+impl FromStrPrefix for char {
+    fn parse_prefix(s: &str) -> Result<(Self, &str), Self::Err> {
+        parse_len(s, s.chars().next().map_or(0, char::len_utf8))
+    }
+}
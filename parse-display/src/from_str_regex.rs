@@ -1,5 +1,9 @@
 use core::num::NonZero;
-use std::{ffi::OsString, path::PathBuf};
+use std::{
+    ffi::OsString,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6},
+    path::PathBuf,
+};
 
 use crate::ANY_REGEX;
 
@@ -7,6 +11,9 @@ use crate::ANY_REGEX;
 ///
 /// When using [`#[derive(FromStr)]`](derive@crate::FromStr) with the [`#[from_str(regex_infer)]`](derive@crate::Display#from_strregex_infer) attribute,
 /// the regex pattern is obtained from the `FromStrRegex` implementation of the field's type.
+///
+/// Since this trait requires `FromStr`, it is not implemented for types that do not implement `FromStr`,
+/// such as `Option`, `Vec`, tuples, `Box`, `Arc`, `Wrapping`, `Saturating` and `Duration`.
 pub trait FromStrRegex: core::str::FromStr {
     /// Returns a regex pattern for strings that might be parseable by [`FromStr`](core::str::FromStr).
     ///
@@ -46,141 +53,192 @@ impl FromStrRegex for bool {
     }
 }
 
-fn regex_uint() -> String {
-    r"[0-9]+".into()
+/// Returns a pattern matching the decimal representations of the integers in `0..=max`,
+/// or in `1..=max` if `nonzero` is `true`.
+///
+/// Leading zeros are allowed as [`FromStr`](core::str::FromStr) for integers accepts them,
+/// but the value is bounded so that adjacent numeric fields can be split by the regex engine.
+fn regex_range(max: u128, nonzero: bool) -> String {
+    let max = max.to_string();
+    let digits = max.as_bytes();
+    let n = digits.len();
+    let mut alts = vec![max.clone()];
+    // numbers with the same number of digits as `max`
+    for (i, &d) in digits.iter().enumerate() {
+        let lo = if i == 0 && (n > 1 || nonzero) {
+            b'1'
+        } else {
+            b'0'
+        };
+        if d > lo {
+            let prefix = &max[..i];
+            let class = regex_digit_class(lo, d - 1);
+            let rest = n - i - 1;
+            if rest == 0 {
+                alts.push(format!("{prefix}{class}"));
+            } else {
+                alts.push(format!("{prefix}{class}[0-9]{{{rest}}}"));
+            }
+        }
+    }
+    // numbers with fewer digits than `max`
+    if n > 2 {
+        alts.push(format!("[1-9][0-9]{{0,{}}}", n - 2));
+    } else if n == 2 {
+        alts.push("[1-9]".into());
+    }
+    if n > 1 && !nonzero {
+        alts.push("0".into());
+    }
+    format!("0*(?:{})", alts.join("|"))
+}
+fn regex_digit_class(lo: u8, hi: u8) -> String {
+    if lo == hi {
+        (lo as char).to_string()
+    } else {
+        format!("[{}-{}]", lo as char, hi as char)
+    }
+}
+
+fn regex_uint(max: u128, nonzero: bool) -> String {
+    format!(r"\+?{}", regex_range(max, nonzero))
 }
 impl FromStrRegex for u8 {
     fn from_str_regex() -> String {
-        regex_uint()
+        regex_uint(u8::MAX as u128, false)
     }
 }
 impl FromStrRegex for NonZero<u8> {
     fn from_str_regex() -> String {
-        regex_uint()
+        regex_uint(u8::MAX as u128, true)
     }
 }
 
 impl FromStrRegex for u16 {
     fn from_str_regex() -> String {
-        regex_uint()
+        regex_uint(u16::MAX as u128, false)
     }
 }
 impl FromStrRegex for NonZero<u16> {
     fn from_str_regex() -> String {
-        regex_uint()
+        regex_uint(u16::MAX as u128, true)
     }
 }
 
 impl FromStrRegex for u32 {
     fn from_str_regex() -> String {
-        regex_uint()
+        regex_uint(u32::MAX as u128, false)
     }
 }
 impl FromStrRegex for NonZero<u32> {
     fn from_str_regex() -> String {
-        regex_uint()
+        regex_uint(u32::MAX as u128, true)
     }
 }
 
 impl FromStrRegex for u64 {
     fn from_str_regex() -> String {
-        regex_uint()
+        regex_uint(u64::MAX as u128, false)
     }
 }
 impl FromStrRegex for NonZero<u64> {
     fn from_str_regex() -> String {
-        regex_uint()
+        regex_uint(u64::MAX as u128, true)
     }
 }
 
 impl FromStrRegex for u128 {
     fn from_str_regex() -> String {
-        regex_uint()
+        regex_uint(u128::MAX, false)
     }
 }
 impl FromStrRegex for NonZero<u128> {
     fn from_str_regex() -> String {
-        regex_uint()
+        regex_uint(u128::MAX, true)
     }
 }
 
 impl FromStrRegex for usize {
     fn from_str_regex() -> String {
-        regex_uint()
+        regex_uint(usize::MAX as u128, false)
     }
 }
 impl FromStrRegex for NonZero<usize> {
     fn from_str_regex() -> String {
-        regex_uint()
+        regex_uint(usize::MAX as u128, true)
     }
 }
 
-fn regex_sint() -> String {
-    r"-?[0-9]+".into()
+fn regex_sint(max: u128, nonzero: bool) -> String {
+    format!(
+        r"(?:-{}|\+?{})",
+        regex_range(max + 1, nonzero),
+        regex_range(max, nonzero)
+    )
 }
 
 impl FromStrRegex for i8 {
     fn from_str_regex() -> String {
-        regex_sint()
+        regex_sint(i8::MAX as u128, false)
     }
 }
 impl FromStrRegex for NonZero<i8> {
     fn from_str_regex() -> String {
-        regex_sint()
+        regex_sint(i8::MAX as u128, true)
     }
 }
 
 impl FromStrRegex for i16 {
     fn from_str_regex() -> String {
-        regex_sint()
+        regex_sint(i16::MAX as u128, false)
     }
 }
 impl FromStrRegex for NonZero<i16> {
     fn from_str_regex() -> String {
-        regex_sint()
+        regex_sint(i16::MAX as u128, true)
     }
 }
 
 impl FromStrRegex for i32 {
     fn from_str_regex() -> String {
-        regex_sint()
+        regex_sint(i32::MAX as u128, false)
     }
 }
 impl FromStrRegex for NonZero<i32> {
     fn from_str_regex() -> String {
-        regex_sint()
+        regex_sint(i32::MAX as u128, true)
     }
 }
 
 impl FromStrRegex for i64 {
     fn from_str_regex() -> String {
-        regex_sint()
+        regex_sint(i64::MAX as u128, false)
     }
 }
 impl FromStrRegex for NonZero<i64> {
     fn from_str_regex() -> String {
-        regex_sint()
+        regex_sint(i64::MAX as u128, true)
     }
 }
 impl FromStrRegex for i128 {
     fn from_str_regex() -> String {
-        regex_sint()
+        regex_sint(i128::MAX as u128, false)
     }
 }
 impl FromStrRegex for NonZero<i128> {
     fn from_str_regex() -> String {
-        regex_sint()
+        regex_sint(i128::MAX as u128, true)
     }
 }
 
 impl FromStrRegex for isize {
     fn from_str_regex() -> String {
-        regex_sint()
+        regex_sint(isize::MAX as u128, false)
     }
 }
 impl FromStrRegex for NonZero<isize> {
     fn from_str_regex() -> String {
-        regex_sint()
+        regex_sint(isize::MAX as u128, true)
     }
 }
 
@@ -197,3 +255,81 @@ impl FromStrRegex for f64 {
         regex_f()
     }
 }
+
+fn regex_ipv4() -> String {
+    // Leading zeros are not allowed.
+    let octet = r"(?:25[0-5]|2[0-4][0-9]|1[0-9]{2}|[1-9]?[0-9])";
+    format!(r"{octet}(?:\.{octet}){{3}}")
+}
+fn regex_ipv6() -> String {
+    let h = "[0-9a-fA-F]{1,4}";
+    let ipv4 = regex_ipv4();
+    let mut alts = vec![format!("(?:{h}:){{6}}(?:{h}:{h}|{ipv4})")];
+    // `::` replaces at least one group, so at most 7 groups can be written around it.
+    for left in 0..=7 {
+        let left_groups = if left == 0 {
+            String::new()
+        } else {
+            format!("{h}(?::{h}){{{}}}", left - 1)
+        };
+        let mut rights = Vec::new();
+        if left <= 6 {
+            rights.push(format!("{h}(?::{h}){{0,{}}}", 6 - left));
+        }
+        if left <= 5 {
+            rights.push(format!("(?:{h}:){{0,{}}}{ipv4}", 5 - left));
+        }
+        let right = if rights.is_empty() {
+            String::new()
+        } else {
+            format!("(?:{})?", rights.join("|"))
+        };
+        alts.push(format!("{left_groups}::{right}"));
+    }
+    format!("(?:{})", alts.join("|"))
+}
+fn regex_port() -> String {
+    regex_range(u16::MAX as u128, false)
+}
+fn regex_socket_v4() -> String {
+    format!("{}:{}", regex_ipv4(), regex_port())
+}
+fn regex_socket_v6() -> String {
+    format!(
+        r"\[{}(?:%{})?\]:{}",
+        regex_ipv6(),
+        regex_range(u32::MAX as u128, false),
+        regex_port()
+    )
+}
+
+impl FromStrRegex for Ipv4Addr {
+    fn from_str_regex() -> String {
+        regex_ipv4()
+    }
+}
+impl FromStrRegex for Ipv6Addr {
+    fn from_str_regex() -> String {
+        regex_ipv6()
+    }
+}
+impl FromStrRegex for IpAddr {
+    fn from_str_regex() -> String {
+        format!("(?:{}|{})", regex_ipv4(), regex_ipv6())
+    }
+}
+impl FromStrRegex for SocketAddrV4 {
+    fn from_str_regex() -> String {
+        regex_socket_v4()
+    }
+}
+impl FromStrRegex for SocketAddrV6 {
+    fn from_str_regex() -> String {
+        regex_socket_v6()
+    }
+}
+impl FromStrRegex for SocketAddr {
+    fn from_str_regex() -> String {
+        format!("(?:{}|{})", regex_socket_v4(), regex_socket_v6())
+    }
+}
@@ -1,12 +1,14 @@
 use ::core::{
     fmt::{self, Display, Formatter},
-    ops::Fn,
+    num::ParseIntError,
+    ops::{Fn, Range},
+    str::FromStr,
 };
 
 #[cfg(feature = "std")]
 pub use super::helpers_std::*;
 
-use crate::{DisplayFormat, FromStrFormat};
+use crate::{DisplayFormat, FromStrFormat, ParseError};
 
 pub struct Formatted<'a, T: ?Sized, F: DisplayFormat<T>> {
     pub value: &'a T,
@@ -56,6 +58,546 @@ where
     }
 }
 
+pub struct OmitFormatHelper<F> {
+    pub omit: bool,
+    pub f: F,
+}
+impl<F> Display for OmitFormatHelper<F>
+where
+    F: Fn(&mut Formatter) -> fmt::Result,
+{
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        if self.omit { Ok(()) } else { (self.f)(f) }
+    }
+}
+
+/// A part of a format that is parsed without regex.
+#[derive(Clone, Copy)]
+pub enum FormatPart {
+    Str(&'static str),
+    Capture,
+}
+
+/// Matches `s` with `parts`.
+///
+/// Each capture matches the shortest string such that the rest of the input can still match,
+/// which is the same as `(?s:.*?)` in regex.
+/// `N` is the number of captures plus one, and the index `0` is the whole input.
+/// If `ignore_case` is true, the literal parts are compared case-insensitively.
+pub fn match_parts<'a, const N: usize>(
+    s: &'a str,
+    parts: &[FormatPart],
+    ignore_case: bool,
+) -> Option<Captures<'a, N>> {
+    let mut ranges = [None; N];
+    ranges[0] = Some((0, s.len()));
+    let mut parts = parts;
+    let mut start = 0;
+    let mut end = s.len();
+    if let [FormatPart::Str(prefix), rest @ ..] = parts {
+        start = prefix_len(s, prefix, ignore_case)?;
+        parts = rest;
+    }
+    if let [rest @ .., FormatPart::Str(suffix)] = parts {
+        end -= suffix_len(&s[start..], suffix, ignore_case)?;
+        parts = rest;
+    }
+    let mut pos = start;
+    let mut index = 1;
+    for (i, part) in parts.iter().enumerate() {
+        match part {
+            FormatPart::Str(p) => {
+                pos += prefix_len(&s[pos..end], p, ignore_case)?;
+            }
+            FormatPart::Capture => {
+                let capture_end = match parts.get(i + 1) {
+                    Some(FormatPart::Str(p)) => pos + find(&s[pos..end], p, ignore_case)?,
+                    Some(FormatPart::Capture) => pos,
+                    None => end,
+                };
+                ranges[index] = Some((pos, capture_end));
+                index += 1;
+                pos = capture_end;
+            }
+        }
+    }
+    if pos != end {
+        return None;
+    }
+    Some(Captures { s, ranges })
+}
+
+fn eq_char_ignore_case(a: char, b: char) -> bool {
+    a == b || a.to_lowercase().eq(b.to_lowercase())
+}
+
+/// Returns the length of the prefix of `s` that matches `p`.
+fn prefix_len(s: &str, p: &str, ignore_case: bool) -> Option<usize> {
+    if !ignore_case {
+        return s.starts_with(p).then_some(p.len());
+    }
+    let mut cs = s.char_indices();
+    for b in p.chars() {
+        let (_, a) = cs.next()?;
+        if !eq_char_ignore_case(a, b) {
+            return None;
+        }
+    }
+    Some(cs.next().map_or(s.len(), |(i, _)| i))
+}
+
+/// Returns the length of the suffix of `s` that matches `p`.
+fn suffix_len(s: &str, p: &str, ignore_case: bool) -> Option<usize> {
+    if !ignore_case {
+        return s.ends_with(p).then_some(p.len());
+    }
+    let mut cs = s.char_indices().rev();
+    let mut start = s.len();
+    for b in p.chars().rev() {
+        let (i, a) = cs.next()?;
+        if !eq_char_ignore_case(a, b) {
+            return None;
+        }
+        start = i;
+    }
+    Some(s.len() - start)
+}
+
+/// Returns the position of the first occurrence of `p` in `s`.
+fn find(s: &str, p: &str, ignore_case: bool) -> Option<usize> {
+    if !ignore_case {
+        return s.find(p);
+    }
+    s.char_indices()
+        .map(|(i, _)| i)
+        .chain([s.len()])
+        .find(|&i| prefix_len(&s[i..], p, true).is_some())
+}
+
+/// Compares `s` with `p` case-insensitively.
+pub fn eq_ignore_case(s: &str, p: &str) -> bool {
+    prefix_len(s, p, true) == Some(s.len())
+}
+
+/// The maximum number of times segments match a piece in [`match_any_order`].
+///
+/// Without the limit, the segments that accept any piece would be tried in all the permutations.
+const ANY_ORDER_LIMIT: usize = 1000;
+
+/// A segment of [`match_any_order`], which matches a piece and writes the ranges of its captures.
+pub type Segment<'a, const N: usize> =
+    &'a dyn Fn(&str, usize, &mut [Option<(usize, usize)>; N]) -> bool;
+
+/// Splits `s` at `delimiter` and matches each piece with one of `segments`, in any order.
+///
+/// Each piece is matched with a segment that has not matched yet and accepts it,
+/// and `f` is called with the captures when all the segments are matched.
+/// If `f` fails, the other segments that accept the pieces are tried,
+/// until segments have matched `ANY_ORDER_LIMIT` times in total.
+/// Returns the first success of `f`, the first error of `f` if all the ways fail or the limit is reached,
+/// or `None` if no way matches.
+///
+/// A segment is called with the piece and its offset, and writes the ranges of its captures in the whole input.
+/// `N` is the number of captures plus one, and `M` is the number of segments.
+pub fn match_any_order<'a, const N: usize, const M: usize, T, E>(
+    s: &'a str,
+    delimiter: &str,
+    segments: &[Segment<'_, N>; M],
+    mut f: impl FnMut(&Captures<'a, N>) -> Result<T, E>,
+) -> Option<Result<T, E>> {
+    let mut pieces = [(0, 0); M];
+    let mut len = 0;
+    let mut offset = 0;
+    for piece in s.split(delimiter) {
+        *pieces.get_mut(len)? = (offset, offset + piece.len());
+        len += 1;
+        offset += piece.len() + delimiter.len();
+    }
+    if len != M {
+        return None;
+    }
+    let mut ranges = [None; N];
+    ranges[0] = Some((0, s.len()));
+    let mut m = AnyOrder {
+        s,
+        pieces,
+        segments,
+        used: [false; M],
+        rest: ANY_ORDER_LIMIT,
+        error: None,
+    };
+    match m.visit(0, &ranges, &mut f) {
+        Some(value) => Some(Ok(value)),
+        None => m.error.map(Err),
+    }
+}
+
+struct AnyOrder<'a, 's, const N: usize, const M: usize, E> {
+    s: &'a str,
+    pieces: [(usize, usize); M],
+    segments: &'s [Segment<'s, N>; M],
+    used: [bool; M],
+    /// The number of times segments can still match a piece.
+    rest: usize,
+    /// The first error of `f`.
+    error: Option<E>,
+}
+impl<'a, const N: usize, const M: usize, E> AnyOrder<'a, '_, N, M, E> {
+    /// Matches the pieces from `index` with the segments that are not used yet.
+    fn visit<T>(
+        &mut self,
+        index: usize,
+        ranges: &[Option<(usize, usize)>; N],
+        f: &mut impl FnMut(&Captures<'a, N>) -> Result<T, E>,
+    ) -> Option<T> {
+        let Some(&(start, end)) = self.pieces.get(index) else {
+            let c = Captures {
+                s: self.s,
+                ranges: *ranges,
+            };
+            return match f(&c) {
+                Ok(value) => Some(value),
+                Err(e) => {
+                    self.error.get_or_insert(e);
+                    None
+                }
+            };
+        };
+        for i in 0..M {
+            if self.used[i] {
+                continue;
+            }
+            if self.rest == 0 {
+                return None;
+            }
+            let mut ranges = *ranges;
+            if (self.segments[i])(&self.s[start..end], start, &mut ranges) {
+                self.rest -= 1;
+                self.used[i] = true;
+                let value = self.visit(index + 1, &ranges, f);
+                self.used[i] = false;
+                if value.is_some() {
+                    return value;
+                }
+            }
+        }
+        None
+    }
+}
+
+/// Format of a struct with `#[display(kv(...))]`.
+pub struct KvFormat {
+    pub sep: &'static str,
+    pub assign: &'static str,
+    /// Pairs of the key and the name of the field.
+    pub keys: &'static [(&'static str, &'static str)],
+    pub ignore_case: bool,
+    pub ignore_unknown_keys: bool,
+}
+
+/// Splits `s` into `key<assign>value` pairs separated by `sep`, and matches the keys with `format.keys`.
+///
+/// The capture `i + 1` is the value of `format.keys[i]`, or `None` if the key does not appear.
+/// `N` is the number of keys plus one, and the index `0` is the whole input.
+pub fn match_kv<'a, const N: usize>(
+    s: &'a str,
+    format: &KvFormat,
+) -> Result<Captures<'a, N>, ParseError> {
+    let mut ranges = [None; N];
+    ranges[0] = Some((0, s.len()));
+    let mut start = 0;
+    while !s.is_empty() {
+        let end = s[start..].find(format.sep).map_or(s.len(), |i| start + i);
+        let Some(i) = s[start..end].find(format.assign) else {
+            return Err(ParseError::with_message("expected a key-value pair.")
+                .with_span(start..end, s.len()));
+        };
+        let key_range = start..start + i;
+        let key = &s[key_range.clone()];
+        let index = format.keys.iter().position(|&(k, _)| {
+            if format.ignore_case {
+                eq_ignore_case(key, k)
+            } else {
+                key == k
+            }
+        });
+        if let Some(index) = index {
+            let range = &mut ranges[index + 1];
+            if range.is_some() {
+                return Err(ParseError::with_message("duplicate key.")
+                    .with_field(format.keys[index].1)
+                    .with_span(key_range, s.len()));
+            }
+            *range = Some((key_range.end + format.assign.len(), end));
+        } else if !format.ignore_unknown_keys {
+            return Err(ParseError::with_message("unknown key.").with_span(key_range, s.len()));
+        }
+        if end == s.len() {
+            break;
+        }
+        start = end + format.sep.len();
+    }
+    Ok(Captures { s, ranges })
+}
+
+/// Writes `key<assign>value` pairs separated by `sep`, for the `Display` of a struct with `#[display(kv(...))]`.
+pub struct KvWriter<'a, 'b> {
+    f: &'a mut Formatter<'b>,
+    sep: &'static str,
+    assign: &'static str,
+    is_first: bool,
+}
+impl<'a, 'b> KvWriter<'a, 'b> {
+    pub fn new(f: &'a mut Formatter<'b>, sep: &'static str, assign: &'static str) -> Self {
+        Self {
+            f,
+            sep,
+            assign,
+            is_first: true,
+        }
+    }
+    pub fn entry(&mut self, key: &str, value: impl Display) -> fmt::Result {
+        if !self.is_first {
+            self.f.write_str(self.sep)?;
+        }
+        self.is_first = false;
+        self.f.write_str(key)?;
+        self.f.write_str(self.assign)?;
+        write!(self.f, "{value}")
+    }
+}
+
+/// Result of [`match_parts`] and [`match_kv`], with the same methods as [`regex::Captures`](https://docs.rs/regex/latest/regex/struct.Captures.html) used in the generated code.
+pub struct Captures<'a, const N: usize> {
+    pub(crate) s: &'a str,
+    pub(crate) ranges: [Option<(usize, usize)>; N],
+}
+impl<'a, const N: usize> Captures<'a, N> {
+    pub fn get(&self, i: usize) -> Option<Match<'a>> {
+        let (start, end) = (*self.ranges.get(i)?)?;
+        Some(Match {
+            s: self.s,
+            start,
+            end,
+        })
+    }
+}
+
+pub struct Match<'a> {
+    s: &'a str,
+    start: usize,
+    end: usize,
+}
+impl<'a> Match<'a> {
+    pub fn as_str(&self) -> &'a str {
+        &self.s[self.start..self.end]
+    }
+    pub fn range(&self) -> Range<usize> {
+        self.start..self.end
+    }
+}
+
+pub trait IntoResultWithError<T, E> {
+    fn into_result_with_error(self) -> Result<T, E>;
+}
+impl<T, E> IntoResultWithError<T, E> for T {
+    fn into_result_with_error(self) -> Result<T, E> {
+        Ok(self)
+    }
+}
+impl<T, E: From<ParseError>> IntoResultWithError<T, E> for Option<T> {
+    fn into_result_with_error(self) -> Result<T, E> {
+        self.ok_or_else(|| ParseError::new().into())
+    }
+}
+impl<T, E: From<X>, X> IntoResultWithError<T, E> for Result<T, X> {
+    fn into_result_with_error(self) -> Result<T, E> {
+        self.map_err(From::from)
+    }
+}
+
+/// Parser for integers displayed with `{:x}`, `{:X}`, `{:o}` or `{:b}`.
+///
+/// Signed integers are parsed as the two's complement representation, which is how they are displayed.
+pub struct Radix {
+    radix: u32,
+    prefix: Option<&'static str>,
+}
+impl Radix {
+    /// If `alternate` is true, the prefix (`0x`, `0o` or `0b`) is accepted.
+    pub const fn new(radix: u32, alternate: bool) -> Self {
+        let prefix = match (alternate, radix) {
+            (true, 16) => Some("0x"),
+            (true, 8) => Some("0o"),
+            (true, 2) => Some("0b"),
+            _ => None,
+        };
+        Self { radix, prefix }
+    }
+    fn strip_prefix<'a>(&self, s: &'a str) -> &'a str {
+        // `+` is displayed by `{:+x}`.
+        let s = s.strip_prefix('+').unwrap_or(s);
+        if let Some(prefix) = self.prefix {
+            s.strip_prefix(prefix).unwrap_or(s)
+        } else {
+            s
+        }
+    }
+    #[cfg(feature = "std")]
+    fn regex_pattern(&self) -> String {
+        let digits = match self.radix {
+            2 => "[01]+",
+            8 => "[0-7]+",
+            16 => "[0-9a-fA-F]+",
+            _ => return crate::ANY_REGEX.into(),
+        };
+        if let Some(prefix) = self.prefix {
+            format!(r"\+?(?:{prefix})?{digits}")
+        } else {
+            format!(r"\+?{digits}")
+        }
+    }
+}
+
+macro_rules! impl_from_str_format_for_radix {
+    ($($t:ty => $u:ty),*) => {
+        $(
+            impl FromStrFormat<$t> for Radix {
+                type Err = ParseIntError;
+                fn parse(&self, s: &str) -> Result<$t, Self::Err> {
+                    <$u>::from_str_radix(self.strip_prefix(s), self.radix).map(|value| value as $t)
+                }
+                #[cfg(feature = "std")]
+                fn regex_pattern(&self) -> String {
+                    self.regex_pattern()
+                }
+            }
+        )*
+    };
+}
+impl_from_str_format_for_radix!(
+    u8 => u8, u16 => u16, u32 => u32, u64 => u64, u128 => u128, usize => usize,
+    i8 => u8, i16 => u16, i32 => u32, i64 => u64, i128 => u128, isize => usize
+);
+
+/// Parser that uses [`FromStr`].
+pub struct ByFromStr;
+impl<T: FromStr> FromStrFormat<T> for ByFromStr {
+    type Err = T::Err;
+    fn parse(&self, s: &str) -> Result<T, Self::Err> {
+        s.parse()
+    }
+}
+
+pub enum Align {
+    Left,
+    Right,
+    Center,
+}
+
+/// Parser for fields displayed with width, fill and alignment (e.g. `{:>8}`, `{:*^12}`).
+///
+/// `width` is `None` if the width is unknown or the field's `Display` may not pad the value.
+pub struct Padding<F> {
+    inner: F,
+    fill: char,
+    align: Align,
+    width: Option<usize>,
+}
+impl<F> Padding<F> {
+    pub const fn new(inner: F, fill: char, align: Align, width: Option<usize>) -> Self {
+        Self {
+            inner,
+            fill,
+            align,
+            width,
+        }
+    }
+    fn strip<'a>(&self, s: &'a str) -> &'a str {
+        if let Some(width) = self.width {
+            if s.chars().count() > width {
+                return s;
+            }
+        }
+        let fill = self.fill;
+        match self.align {
+            Align::Left => s.trim_end_matches(fill),
+            Align::Right => s.trim_start_matches(fill),
+            Align::Center => s.trim_matches(fill),
+        }
+    }
+}
+impl<T, F: FromStrFormat<T>> FromStrFormat<T> for Padding<F> {
+    type Err = F::Err;
+    fn parse(&self, s: &str) -> Result<T, Self::Err> {
+        let value = self.strip(s);
+        match self.inner.parse(value) {
+            Err(_) if value.is_empty() && !s.is_empty() => {
+                // The value itself may consist of fill characters. (e.g. `0` displayed with `{:0>4}`)
+                let (i, _) = s.char_indices().next_back().unwrap();
+                self.inner.parse(&s[i..])
+            }
+            r => r,
+        }
+    }
+    #[cfg(feature = "std")]
+    fn regex_pattern(&self) -> String {
+        if let Some(width) = self.width {
+            // Not exactly `width`: `Display` writes values longer than `width` without padding.
+            return format!("(?s:.{{{width},}}?)");
+        }
+        let inner = self.inner.regex_pattern();
+        let fill = regex_syntax::escape(self.fill.encode_utf8(&mut [0; 4]));
+        match self.align {
+            Align::Left => format!("(?:{inner})(?:{fill})*"),
+            Align::Right => format!("(?:{fill})*(?:{inner})"),
+            Align::Center => format!("(?:{fill})*(?:{inner})(?:{fill})*"),
+        }
+    }
+}
+
+/// Returns `true` if `args` is formatted as `s`.
+pub fn is_displayed_as(s: &str, args: fmt::Arguments) -> bool {
+    struct Matcher<'a>(&'a str);
+    impl fmt::Write for Matcher<'_> {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            self.0 = self.0.strip_prefix(s).ok_or(fmt::Error)?;
+            Ok(())
+        }
+    }
+    let mut m = Matcher(s);
+    fmt::write(&mut m, args).is_ok() && m.0.is_empty()
+}
+
+pub fn parse_as<T: FromStr>(_: &T, s: &str) -> Result<T, T::Err> {
+    s.parse()
+}
+
+/// Wrapper of the error returned by a field's parser.
+///
+/// [`AttachSource`] is used if the error implements [`Error`](core::error::Error), otherwise [`AttachNoSource`] is used.
+pub struct SourceError<E>(pub E);
+
+pub trait AttachSource {
+    fn attach_to(self, e: ParseError) -> ParseError;
+}
+
+#[cfg(feature = "alloc")]
+impl<E: core::error::Error + Send + Sync + 'static> AttachSource for SourceError<E> {
+    fn attach_to(self, e: ParseError) -> ParseError {
+        e.with_source(self.0)
+    }
+}
+
+pub trait AttachNoSource {
+    fn attach_to(self, e: ParseError) -> ParseError;
+}
+impl<E> AttachNoSource for &SourceError<E> {
+    fn attach_to(self, e: ParseError) -> ParseError {
+        e
+    }
+}
+
 #[deprecated(
     note = "`#[from_str(default_fields)]` will be removed in a future version. Use `#[from_str(default)]` instead."
 )]
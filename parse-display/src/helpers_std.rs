@@ -1,11 +1,13 @@
-use core::mem;
+use core::{mem, ops::ControlFlow};
 use std::collections::HashMap;
-use std::{borrow::Cow, fmt};
+use std::{borrow::Cow, fmt, str::FromStr};
 
 use regex::Regex;
 use regex_syntax::ast::{Ast, Flags, GroupKind};
 
-use crate::{ANY_REGEX, DisplayFormat, FromStrFormat, FromStrRegex};
+use crate::{
+    ANY_REGEX, DisplayFormat, FindCaptures, FromStrFormat, FromStrRegex, helpers::Captures,
+};
 
 pub use regex;
 
@@ -61,10 +63,29 @@ pub struct Parser {
     pub re: Regex,
     pub re_str: String,
     pub ss: Vec<Option<String>>,
+    elements: Vec<Element>,
 }
+
+/// A part of the format matched one by one by [`Parser::backtrack`].
+struct Element {
+    re: Regex,
+    /// Pairs of the index of the capture in `re` and in [`Parser::re`].
+    captures: Vec<(usize, usize)>,
+}
+
 impl Parser {
     #[track_caller]
     pub fn new(s: &str, with: &mut [(&str, Option<(String, Ast)>)]) -> Self {
+        Self::with_backtrack(s, &[], with)
+    }
+
+    /// Creates a parser that can also match the input with `elements` one by one, for `#[from_str(backtrack)]`.
+    #[track_caller]
+    pub fn with_backtrack(
+        s: &str,
+        elements: &[&str],
+        with: &mut [(&str, Option<(String, Ast)>)],
+    ) -> Self {
         let mut asts: HashMap<&str, &Ast> = HashMap::new();
         let mut ss = Vec::new();
         for (capture_name, item) in with {
@@ -76,19 +97,29 @@ impl Parser {
             }
         }
         let mut ast = regex_syntax::ast::parse::Parser::new().parse(s).unwrap();
-        replace_ast(&mut ast, &mut |ast| {
-            if let Ast::Group(g) = ast {
-                if let GroupKind::CaptureName { name, .. } = &g.kind {
-                    if let Some(ast) = asts.get(name.name.as_str()) {
-                        *g.ast = (*ast).clone();
-                        return Ok(false);
-                    }
-                }
-            }
-            Ok(true)
-        })
-        .unwrap();
+        replace_captures(&mut ast, &asts);
         let re = Regex::new(&ast.to_string()).unwrap();
+        let elements = elements
+            .iter()
+            .map(|s| {
+                let mut ast = regex_syntax::ast::parse::Parser::new().parse(s).unwrap();
+                replace_captures(&mut ast, &asts);
+                let element_re = Regex::new(&ast.to_string()).unwrap();
+                let captures = element_re
+                    .capture_names()
+                    .enumerate()
+                    .filter_map(|(i, name)| {
+                        let name = name?;
+                        let index = re.capture_names().position(|n| n == Some(name))?;
+                        Some((i, index))
+                    })
+                    .collect();
+                Element {
+                    re: element_re,
+                    captures,
+                }
+            })
+            .collect();
         replace_ast(&mut ast, &mut |ast| {
             if let Ast::Group(g) = ast {
                 if let GroupKind::CaptureName { .. } = &g.kind {
@@ -102,10 +133,137 @@ impl Parser {
         })
         .unwrap();
         let re_str = ast.to_string();
-        Self { re, re_str, ss }
+        Self {
+            re,
+            re_str,
+            ss,
+            elements,
+        }
+    }
+
+    /// Calls `f` with the captures of the ways to split `s` into the elements, until `f` returns `Ok` or `limit` ways are tried.
+    ///
+    /// The first way is the match of [`Parser::re`], and its error is returned if all the ways fail.
+    /// Returns `None` if `s` does not match [`Parser::re`].
+    /// `N` is the number of the captures of [`Parser::re`], including the whole input.
+    pub fn backtrack<'a, const N: usize, T, E>(
+        &self,
+        s: &'a str,
+        limit: usize,
+        mut f: impl FnMut(&Captures<'a, N>) -> Result<T, E>,
+    ) -> Option<Result<T, E>> {
+        let c = self.re.captures(s)?;
+        let mut first = [None; N];
+        for (i, range) in first.iter_mut().enumerate() {
+            *range = c.get(i).map(|m| (m.start(), m.end()));
+        }
+        let e = match f(&Captures { s, ranges: first }) {
+            Ok(value) => return Some(Ok(value)),
+            Err(e) => e,
+        };
+        let mut b = Backtrack {
+            elements: &self.elements,
+            s,
+            first,
+            rest: limit.saturating_sub(1),
+            dead: vec![false; self.elements.len() * (s.len() + 1)],
+            f,
+        };
+        let mut ranges = [None; N];
+        ranges[0] = Some((0, s.len()));
+        match b.visit(0, 0, &mut ranges) {
+            ControlFlow::Break(Some(value)) => Some(Ok(value)),
+            _ => Some(Err(e)),
+        }
     }
 }
 
+struct Backtrack<'a, 'p, const N: usize, F> {
+    elements: &'p [Element],
+    s: &'a str,
+    first: [Option<(usize, usize)>; N],
+    /// The number of the ways that can still be tried.
+    rest: usize,
+    /// Whether the rest of the input from `pos` cannot match the elements from `index`, at `index * (s.len() + 1) + pos`.
+    dead: Vec<bool>,
+    f: F,
+}
+impl<'a, const N: usize, T, E, F> Backtrack<'a, '_, N, F>
+where
+    F: FnMut(&Captures<'a, N>) -> Result<T, E>,
+{
+    /// Matches the elements from `index` with the input from `pos`.
+    ///
+    /// Breaks with the value if a way succeeds, or with `None` if the limit is reached.
+    /// Continues with whether the elements could match the rest of the input.
+    fn visit(
+        &mut self,
+        index: usize,
+        pos: usize,
+        ranges: &mut [Option<(usize, usize)>; N],
+    ) -> ControlFlow<Option<T>, bool> {
+        let Some(element) = self.elements.get(index) else {
+            if pos != self.s.len() {
+                return ControlFlow::Continue(false);
+            }
+            if *ranges == self.first {
+                return ControlFlow::Continue(true);
+            }
+            if self.rest == 0 {
+                return ControlFlow::Break(None);
+            }
+            self.rest -= 1;
+            let c = Captures {
+                s: self.s,
+                ranges: *ranges,
+            };
+            return match (self.f)(&c) {
+                Ok(value) => ControlFlow::Break(Some(value)),
+                Err(_) => ControlFlow::Continue(true),
+            };
+        };
+        let dead_index = index * (self.s.len() + 1) + pos;
+        if self.dead[dead_index] {
+            return ControlFlow::Continue(false);
+        }
+        let mut matched = false;
+        let last = index + 1 == self.elements.len();
+        for end in (pos..=self.s.len()).rev() {
+            if !self.s.is_char_boundary(end) {
+                continue;
+            }
+            if let Some(c) = element.re.captures(&self.s[pos..end]) {
+                for &(i, index) in &element.captures {
+                    ranges[index] = c.get(i).map(|m| (pos + m.start(), pos + m.end()));
+                }
+                matched |= self.visit(index + 1, end, ranges)?;
+            }
+            if last {
+                break;
+            }
+        }
+        if !matched {
+            self.dead[dead_index] = true;
+        }
+        ControlFlow::Continue(matched)
+    }
+}
+
+fn replace_captures(ast: &mut Ast, asts: &HashMap<&str, &Ast>) {
+    replace_ast(ast, &mut |ast| {
+        if let Ast::Group(g) = ast {
+            if let GroupKind::CaptureName { name, .. } = &g.kind {
+                if let Some(ast) = asts.get(name.name.as_str()) {
+                    *g.ast = (*ast).clone();
+                    return Ok(false);
+                }
+            }
+        }
+        Ok(true)
+    })
+    .unwrap();
+}
+
 #[track_caller]
 pub fn build_regex(s: &str, with: &[(&str, Option<Ast>)]) -> Regex {
     let with: HashMap<&str, &Ast> = with
@@ -170,6 +328,26 @@ fn replace_ast(
 
 type ReplaceAstResult<T = ()> = Result<T, String>;
 
+/// Creates [`FindCaptures`] from the captures of the value found by `#[from_str(find)]`.
+///
+/// `fields` are pairs of the name of a field and the index of its capture.
+pub fn find_captures<'a, T: FromStr>(
+    text: &'a str,
+    c: &regex::Captures<'a>,
+    fields: &[(&'static str, usize)],
+    value: Result<T, T::Err>,
+) -> FindCaptures<'a, T> {
+    FindCaptures {
+        text,
+        range: c.get_match().range(),
+        fields: fields
+            .iter()
+            .map(|&(name, index)| (name, c.get(index).map(|m| m.range())))
+            .collect(),
+        value,
+    }
+}
+
 pub struct RegexInfer;
 impl<T: fmt::Display> DisplayFormat<T> for RegexInfer {
     fn write(&self, f: &mut fmt::Formatter, value: &T) -> fmt::Result {
@@ -0,0 +1,186 @@
+//! Parsing of line-oriented input.
+
+use std::{
+    fmt::{self, Display, Formatter},
+    io::{self, BufRead, Lines},
+    marker::PhantomData,
+    str::FromStr,
+};
+
+use crate::ParseError;
+
+/// Returns an iterator that parses each line of `reader` as `T`.
+///
+/// The errors of the iterator carry the line number and the line that failed to parse.
+/// The iterator ends after an error reading `reader`.
+/// Blank lines and comment lines are parsed as well, unless they are skipped by
+/// [`skip_blank_lines`](ParseLines::skip_blank_lines) and [`skip_comment_lines`](ParseLines::skip_comment_lines).
+///
+/// ```
+/// use parse_display::{FromStr, io::parse_lines};
+///
+/// #[derive(FromStr, PartialEq, Debug)]
+/// #[display("{name}={value}")]
+/// struct Record {
+///     name: String,
+///     value: u32,
+/// }
+///
+/// let input = "# settings\na=1\n\nb=x\n";
+/// let mut records = parse_lines::<Record, _>(input.as_bytes())
+///     .skip_blank_lines()
+///     .skip_comment_lines();
+/// assert_eq!(records.next().unwrap().unwrap(), Record { name: "a".into(), value: 1 });
+/// let e = records.next().unwrap().unwrap_err();
+/// assert_eq!(e.line_number(), 4);
+/// assert_eq!(e.line(), "b=x");
+/// assert!(records.next().is_none());
+/// ```
+pub fn parse_lines<T: FromStr, R: BufRead>(reader: R) -> ParseLines<T, R> {
+    ParseLines {
+        lines: reader.lines(),
+        line_number: 0,
+        io_failed: false,
+        skip_blank_lines: false,
+        skip_comment_lines: false,
+        _phantom: PhantomData,
+    }
+}
+
+/// An iterator that parses each line of a reader, created by [`parse_lines`].
+pub struct ParseLines<T, R> {
+    lines: Lines<R>,
+    line_number: usize,
+    io_failed: bool,
+    skip_blank_lines: bool,
+    skip_comment_lines: bool,
+    _phantom: PhantomData<fn() -> T>,
+}
+
+impl<T, R> ParseLines<T, R> {
+    /// Skips the lines that consist only of whitespace.
+    pub fn skip_blank_lines(mut self) -> Self {
+        self.skip_blank_lines = true;
+        self
+    }
+
+    /// Skips the lines whose first non-whitespace character is `#`.
+    pub fn skip_comment_lines(mut self) -> Self {
+        self.skip_comment_lines = true;
+        self
+    }
+
+    fn is_skipped(&self, line: &str) -> bool {
+        let line = line.trim_start();
+        (self.skip_blank_lines && line.is_empty())
+            || (self.skip_comment_lines && line.starts_with('#'))
+    }
+}
+
+impl<T: FromStr, R: BufRead> Iterator for ParseLines<T, R> {
+    type Item = Result<T, LineError<T::Err>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.io_failed {
+            return None;
+        }
+        loop {
+            let line = self.lines.next()?;
+            self.line_number += 1;
+            let line = match line {
+                Ok(line) => line,
+                Err(e) => {
+                    self.io_failed = true;
+                    return Some(Err(LineError {
+                        line_number: self.line_number,
+                        line: String::new(),
+                        kind: LineErrorKind::Io(e),
+                    }));
+                }
+            };
+            if self.is_skipped(&line) {
+                continue;
+            }
+            return Some(line.parse().map_err(|e| LineError {
+                line_number: self.line_number,
+                line,
+                kind: LineErrorKind::Parse(e),
+            }));
+        }
+    }
+}
+
+/// Error type returned by [`ParseLines`].
+///
+/// The message contains only the line number and the line, and the cause is available from [`kind`](Self::kind) or [`source`](std::error::Error::source).
+#[derive(Debug)]
+pub struct LineError<E = ParseError> {
+    line_number: usize,
+    line: String,
+    kind: LineErrorKind<E>,
+}
+
+/// The cause of [`LineError`].
+#[derive(Debug)]
+pub enum LineErrorKind<E = ParseError> {
+    /// The line could not be read.
+    Io(io::Error),
+    /// The line could not be parsed.
+    Parse(E),
+}
+
+impl<E> LineError<E> {
+    /// Returns the 1-based number of the line where the error occurred.
+    pub fn line_number(&self) -> usize {
+        self.line_number
+    }
+
+    /// Returns the line that failed to parse.
+    ///
+    /// Empty if the line could not be read.
+    pub fn line(&self) -> &str {
+        &self.line
+    }
+
+    /// Returns the cause of the error.
+    pub fn kind(&self) -> &LineErrorKind<E> {
+        &self.kind
+    }
+
+    /// Returns the cause of the error, consuming `self`.
+    pub fn into_kind(self) -> LineErrorKind<E> {
+        self.kind
+    }
+
+    /// Returns the error returned by [`FromStr`], if the line could not be parsed.
+    pub fn parse_error(&self) -> Option<&E> {
+        match &self.kind {
+            LineErrorKind::Parse(e) => Some(e),
+            LineErrorKind::Io(_) => None,
+        }
+    }
+}
+
+impl<E> Display for LineError<E> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match &self.kind {
+            LineErrorKind::Io(_) => write!(f, "line {}: read failed.", self.line_number),
+            LineErrorKind::Parse(_) => {
+                write!(
+                    f,
+                    "line {}: parse failed. (`{}`)",
+                    self.line_number, self.line
+                )
+            }
+        }
+    }
+}
+
+impl<E: std::error::Error + 'static> std::error::Error for LineError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match &self.kind {
+            LineErrorKind::Io(e) => Some(e),
+            LineErrorKind::Parse(e) => Some(e),
+        }
+    }
+}
@@ -27,11 +27,26 @@
 //! assert_eq!(Y::VarA.to_string(), "var_a");
 //! assert_eq!("var_a".parse(), Ok(Y::VarA));
 //! ```
+//!
+//! ## `no_std` support
+//!
+//! Without the `std` feature, `#[derive(FromStr)]` supports formats consisting only of fixed strings and fields (e.g. `"{a}-{b}"`),
+//! which are parsed without regex and allocation.
+//! `#[from_str(regex = ...)]`, `#[from_str(regex_infer)]`, `#[display(with = ...)]` and `#[display(opt)]` require the `std` feature.
+//!
+//! The `alloc` feature, which is enabled by `std`, keeps the source error and the suggestions of [`ParseError`] in `no_std` builds.
 #![cfg_attr(not(feature = "std"), no_std)]
 #![cfg_attr(feature = "docs", feature(doc_cfg))]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "alloc")]
+use alloc::{boxed::Box, string::String, vec::Vec};
+
 use core::convert::Infallible;
 use core::fmt::{Display, Formatter, Result};
+use core::ops::Range;
 
 #[cfg(test)]
 mod tests;
@@ -42,12 +57,25 @@ pub mod helpers;
 #[cfg(feature = "std")]
 mod helpers_std;
 
+mod from_str_prefix;
+
 #[cfg(feature = "std")]
 mod from_str_regex;
 
+#[cfg(feature = "std")]
+mod from_str_find;
+
+#[cfg(feature = "std")]
+pub mod io;
+
+pub use from_str_prefix::FromStrPrefix;
+
 #[cfg(feature = "std")]
 pub use from_str_regex::FromStrRegex;
 
+#[cfg(feature = "std")]
+pub use from_str_find::{FindCaptures, FindItem, FindIter, FromStrFind};
+
 // #[include_doc("display.md", start)]
 /// Derive [`Display`].
 ///
@@ -60,20 +88,33 @@ pub use from_str_regex::FromStrRegex;
 ///
 /// Helper attributes can be written in the following positions.
 ///
-/// | attribute                                                     | `#[display]` | `#[from_str]` | struct | enum | variant | field |
-/// | ------------------------------------------------------------- | ------------ | ------------- | ------ | ---- | ------- | ----- |
-/// | [`#[display("...")]`](#display)                               | ✔            |               | ✔      | ✔    | ✔       | ✔     |
-/// | [`#[display(style = "...")]`](#displaystyle--)                | ✔            |               |        | ✔    | ✔       |       |
-/// | [`#[display(with = ...)]`](#displaywith---from_strwith--)     | ✔            | ✔             |        |      |         | ✔     |
-/// | [`#[display(opt)]`](#displayopt)                              |              |               |        |      |         | ✔     |
-/// | [`#[display(bound(...))]`](#displaybound-from_strbound)       | ✔            | ✔             | ✔      | ✔    | ✔       | ✔     |
-/// | [`#[display(crate = ...)]`](#displaycrate--)                  | ✔            |               | ✔      | ✔    |         |       |
-/// | [`#[display(dump)]`](#displaydump-from_strdump)               | ✔            | ✔             | ✔      | ✔    |         |       |
-/// | [`#[from_str(regex = "...")]`](#from_strregex--)              |              | ✔             | ✔      | ✔    | ✔       | ✔     |
-/// | [`#[from_str(regex_infer)]`](#from_strregex_infer)            |              | ✔             | ✔      | ✔    | ✔       | ✔     |
-/// | [`#[from_str(new = ...)]`](#from_strnew--)                    |              | ✔             | ✔      |      | ✔       |       |
-/// | [`#[from_str(ignore)]`](#from_strignore)                      |              | ✔             |        |      | ✔       |       |
-/// | [`#[from_str(default)]`](#from_strdefault)                    |              | ✔             | ✔      |      |         | ✔     |
+/// | attribute                                                          | `#[display]` | `#[from_str]` | struct | enum | variant | field |
+/// | ------------------------------------------------------------------ | ------------ | ------------- | ------ | ---- | ------- | ----- |
+/// | [`#[display("...")]`](#display)                                    | ✔            |               | ✔      | ✔    | ✔       | ✔     |
+/// | [`#[display(style = "...")]`](#displaystyle--)                     | ✔            |               | ✔      | ✔    | ✔       |       |
+/// | [`#[display(with = ...)]`](#displaywith---from_strwith--)          | ✔            | ✔             |        |      |         | ✔     |
+/// | [`#[display(opt)]`](#displayopt)                                   |              |               |        |      |         | ✔     |
+/// | [`#[display(omit_if = ...)]`](#displayomit_if---displayopt_empty)  |              |               |        |      |         | ✔     |
+/// | [`#[display(opt_empty)]`](#displayomit_if---displayopt_empty)      |              |               |        |      |         | ✔     |
+/// | [`#[display(kv)]`](#displaykv)                                     | ✔            |               | ✔      |      |         |       |
+/// | [`#[display(bound(...))]`](#displaybound-from_strbound)            | ✔            | ✔             | ✔      | ✔    | ✔       | ✔     |
+/// | [`#[display(crate = ...)]`](#displaycrate--)                       | ✔            |               | ✔      | ✔    |         |       |
+/// | [`#[display(dump)]`](#displaydump-from_strdump)                    | ✔            | ✔             | ✔      | ✔    |         |       |
+/// | [`#[from_str(regex = "...")]`](#from_strregex--)                   |              | ✔             | ✔      | ✔    | ✔       | ✔     |
+/// | [`#[from_str(regex_infer)]`](#from_strregex_infer)                 |              | ✔             | ✔      | ✔    | ✔       | ✔     |
+/// | [`#[from_str(strict)]`](#from_strstrict)                           |              | ✔             | ✔      | ✔    | ✔       | ✔     |
+/// | [`#[from_str(ignore_case)]`](#from_strignore_case)                 |              | ✔             | ✔      | ✔    | ✔       |       |
+/// | [`#[from_str(alias = "...")]`](#from_stralias---from_straliases--) |              | ✔             | ✔      |      | ✔       |       |
+/// | [`#[from_str(other)]`](#from_strother)                             |              | ✔             |        |      | ✔       |       |
+/// | [`#[from_str(ignore_unknown_keys)]`](#from_strignore_unknown_keys) |              | ✔             | ✔      |      |         |       |
+/// | [`#[from_str(any_order)]`](#from_strany_order)                     |              | ✔             | ✔      |      |         |       |
+/// | [`#[from_str(backtrack)]`](#from_strbacktrack)                     |              | ✔             | ✔      | ✔    | ✔       |       |
+/// | [`#[from_str(prefix)]`](#from_strprefix)                           |              | ✔             | ✔      | ✔    |         |       |
+/// | [`#[from_str(find)]`](#from_strfind)                               |              | ✔             | ✔      | ✔    |         |       |
+/// | [`#[from_str(new = ...)]`](#from_strnew--)                         |              | ✔             | ✔      |      | ✔       |       |
+/// | [`#[from_str(error = ...)]`](#from_strerror--)                     |              | ✔             | ✔      | ✔    |         |       |
+/// | [`#[from_str(ignore)]`](#from_strignore)                           |              | ✔             |        |      | ✔       |       |
+/// | [`#[from_str(default)]`](#from_strdefault)                         |              | ✔             | ✔      |      |         | ✔     |
 ///
 /// ## `#[display("...")]`
 ///
@@ -86,6 +127,7 @@ pub use from_str_regex::FromStrRegex;
 /// | [`{a}`, `{b}`, `{1}`] | ✔      | ✔    | ✔       | ✔     | Use a field with the specified name.                                                |
 /// | [`{}`]                |        | ✔    | ✔       |       | Use a variant name of enum.                                                         |
 /// | [`{}`,`{:x}`, `{:?}`] |        |      |         | ✔     | Use the field itself.                                                               |
+/// | [`{#name}`]           |        |      |         | ✔     | Use the field name with the style applied.                                          |
 /// | [`{:x}`, `{:?}`]      | ✔      | ✔    |         |       | Use format traits other than [`Display`] for `self`. (e.g. [`LowerHex`], [`Debug`]) |
 /// | [`{a.b.c}`]           | ✔      | ✔    | ✔       | ✔     | Use a nested field.                                                                 |
 ///
@@ -93,6 +135,7 @@ pub use from_str_regex::FromStrRegex;
 /// [`{a}`, `{b}`, `{1}`]: #struct-format
 /// [`{}`]: #variant-name
 /// [`{}`,`{:x}`, `{:?}`]: #field-format
+/// [`{#name}`]: #field-name
 /// [`{:x}`, `{:?}`]: #format-parameter
 /// [`{a.b.c}`]: #nested-field
 ///
@@ -251,6 +294,27 @@ pub use from_str_regex::FromStrRegex;
 /// assert_eq!("this is A ___10___".parse(), Ok(MyEnum::VarA(10)));
 /// ```
 ///
+/// ### Field name
+///
+/// In field format, `{#name}` means the field name.
+/// The field name is converted with the style specified by [`#[display(style = "...")]`](#displaystyle--) on the struct or variant.
+///
+/// ```rust
+/// use parse_display::{Display, FromStr};
+///
+/// #[derive(Display, FromStr, PartialEq, Debug)]
+/// #[display("{user_id}, {full_name}", style = "kebab-case")]
+/// struct MyStruct {
+///   #[display("{#name}={}")]
+///   user_id: u32,
+///   #[display("{#name}={}")]
+///   full_name: String,
+/// }
+/// let value = MyStruct { user_id: 10, full_name: "abc".into() };
+/// assert_eq!(value.to_string(), "user-id=10, full-name=abc");
+/// assert_eq!("user-id=10, full-name=abc".parse(), Ok(value));
+/// ```
+///
 /// ### Format parameter
 ///
 /// Like `std::format!()`, format parameter can be specified.
@@ -266,6 +330,46 @@ pub use from_str_regex::FromStrRegex;
 /// assert_eq!(WithFormatParameter { a:5 }.to_string(), "0005");
 /// ```
 ///
+/// For fields of primitive integer types, `FromStr` also honours the radix specified by `{:x}`, `{:X}`, `{:o}` and `{:b}`.
+/// If `#` is specified (e.g. `{:#x}`), the `0x`, `0o` or `0b` prefix is accepted but not required.
+/// This is not applied if `#[from_str(regex = "...")]` or `#[from_str(with = ...)]` is specified for the field.
+///
+/// ```rust
+/// use parse_display::{Display, FromStr};
+///
+/// #[derive(Display, FromStr, PartialEq, Debug)]
+/// #[display("{r:02x}:{g:02x}:{b:02x}")]
+/// struct Rgb {
+///   r: u8,
+///   g: u8,
+///   b: u8,
+/// }
+/// assert_eq!(Rgb { r: 255, g: 0, b: 16 }.to_string(), "ff:00:10");
+/// assert_eq!("ff:00:10".parse(), Ok(Rgb { r: 255, g: 0, b: 16 }));
+///
+/// #[derive(Display, FromStr, PartialEq, Debug)]
+/// #[display("{0:#x}")]
+/// struct Addr(u32);
+/// assert_eq!(Addr(0x1f).to_string(), "0x1f");
+/// assert_eq!("0x1f".parse(), Ok(Addr(0x1f)));
+/// ```
+///
+/// The width, fill and alignment (e.g. `{:>8}`, `{:*^12}`) are also honoured. The fill characters are removed before parsing the field.
+/// For numbers, `bool`, `char` and `String`, which pad the value when displayed, the field must be at least as long as the width, so adjacent fixed-width fields can be parsed without separators.
+///
+/// ```rust
+/// use parse_display::{Display, FromStr};
+///
+/// #[derive(Display, FromStr, PartialEq, Debug)]
+/// #[display("{id:>4}{name:<6}|")]
+/// struct Row {
+///   id: u32,
+///   name: String,
+/// }
+/// assert_eq!(Row { id: 42, name: "abc".into() }.to_string(), "  42abc   |");
+/// assert_eq!("  42abc   |".parse(), Ok(Row { id: 42, name: "abc".into() }));
+/// ```
+///
 /// When `{}` is used within `#[display("...")]` set for an enum, and if a format trait is added to `{}` such as `{:?}`, the meaning changes from "variant name" to "a string using a trait other than Display for self."
 ///
 /// ```rust
@@ -334,6 +438,12 @@ pub use from_str_regex::FromStrRegex;
 /// - `Title case`
 /// - `title case`
 /// - `TITLE CASE`
+/// - `flatcase`
+/// - `UPPERFLATCASE`
+/// - `COBOL-CASE`
+/// - `Train-Case`
+/// - `dot.case`
+/// - `path/case`
 ///
 /// ```rust
 /// use parse_display::{Display, FromStr};
@@ -379,6 +489,18 @@ pub use from_str_regex::FromStrRegex;
 ///   VarL,
 ///   #[display(style = "TITLE CASE")]
 ///   VarM,
+///   #[display(style = "flatcase")]
+///   VarN,
+///   #[display(style = "UPPERFLATCASE")]
+///   VarO,
+///   #[display(style = "COBOL-CASE")]
+///   VarP,
+///   #[display(style = "Train-Case")]
+///   VarQ,
+///   #[display(style = "dot.case")]
+///   VarR,
+///   #[display(style = "path/case")]
+///   VarS,
 /// }
 /// assert_eq!(StyleExample::VarA1.to_string(), "VarA1");
 /// assert_eq!(StyleExample::varA2.to_string(), "varA2");
@@ -395,8 +517,39 @@ pub use from_str_regex::FromStrRegex;
 /// assert_eq!(StyleExample::VarK.to_string(), "Var k");
 /// assert_eq!(StyleExample::VarL.to_string(), "var l");
 /// assert_eq!(StyleExample::VarM.to_string(), "VAR M");
+/// assert_eq!(StyleExample::VarN.to_string(), "varn");
+/// assert_eq!(StyleExample::VarO.to_string(), "VARO");
+/// assert_eq!(StyleExample::VarP.to_string(), "VAR-P");
+/// assert_eq!(StyleExample::VarQ.to_string(), "Var-Q");
+/// assert_eq!(StyleExample::VarR.to_string(), "var.r");
+/// assert_eq!(StyleExample::VarS.to_string(), "var/s");
 /// ```
 ///
+/// The case and the word separator can also be specified in the form `{case}:{sep=...}`.
+/// The following cases are available for `{case}`.
+///
+/// - `{lower}` : all lowercase (e.g. `abc_def`)
+/// - `{upper}` : all uppercase (e.g. `ABC_DEF`)
+/// - `{title}` : capitalize each word (e.g. `Abc_Def`)
+/// - `{camel}` : capitalize each word except the first word (e.g. `abc_Def`)
+///
+/// ```rust
+/// use parse_display::{Display, FromStr};
+///
+/// #[derive(Display, FromStr, PartialEq, Debug)]
+/// #[display(style = "{lower}:{sep=::}")]
+/// enum MyEnum {
+///   VarA,
+///   #[display(style = "{title}:{sep=_}")]
+///   VarB,
+/// }
+/// assert_eq!(MyEnum::VarA.to_string(), "var::a");
+/// assert_eq!(MyEnum::VarB.to_string(), "Var_B");
+/// assert_eq!("var::a".parse(), Ok(MyEnum::VarA));
+/// ```
+///
+/// If `#[display(style = "...")]` is specified for a struct or variant, the style is also applied to the field names written as [`{#name}`](#field-name).
+///
 /// ## `#[display(opt)]`
 ///
 /// When applied to an `Option<T>` field, this attribute makes the field display an empty string for `None` and use `T`'s trait implementations directly (not `Option<T>`'s, but `T`'s `Display`, `FromStr`, etc.) for `Some(T)`.
@@ -415,6 +568,108 @@ pub use from_str_regex::FromStrRegex;
 ///
 /// When the field is `None`, not just the placeholder but the entire format string for that field is omitted from the output. In the example above, when `a` is `None`, the output is `""` rather than `"a="`.
 ///
+/// The string displayed for `None` can be specified as `#[display(opt = "...")]`.
+/// `FromStr` parses that string as `None`.
+///
+/// ```rust
+/// use parse_display::{Display, FromStr};
+///
+/// #[derive(Display, FromStr, PartialEq, Debug)]
+/// #[display("{a},{b}")]
+/// struct X {
+///     #[display(opt = "-")]
+///     a: Option<u32>,
+///     #[display("b={}", opt = "null")]
+///     b: Option<u32>,
+/// }
+/// assert_eq!(X { a: None, b: None }.to_string(), "-,null");
+/// assert_eq!("-,b=10".parse(), Ok(X { a: None, b: Some(10) }));
+/// ```
+///
+/// ## `#[display(omit_if = ...)]`, `#[display(opt_empty)]`
+///
+/// `#[display(omit_if = ...)]` omits the entire format string of a field from the output when the specified function returns `true` for the field.
+/// The function takes a reference to the field and returns `bool`.
+/// `#[display(opt_empty)]` is a shorthand for omitting the field when its `is_empty()` method returns `true`.
+///
+/// `FromStr` uses `Default::default()` for the field when its format string is absent from the input.
+///
+/// ```rust
+/// use parse_display::{Display, FromStr};
+///
+/// fn is_zero(value: &u32) -> bool {
+///     *value == 0
+/// }
+///
+/// #[derive(Display, FromStr, PartialEq, Debug)]
+/// #[display("{a}{b}{c}")]
+/// struct X {
+///     a: u32,
+///     #[display(" b={}", omit_if = is_zero)]
+///     b: u32,
+///     #[display(" c={}", opt_empty)]
+///     c: String,
+/// }
+/// assert_eq!(X { a: 1, b: 0, c: "".into() }.to_string(), "1");
+/// assert_eq!(X { a: 1, b: 2, c: "x".into() }.to_string(), "1 b=2 c=x");
+/// assert_eq!("1 c=x".parse(), Ok(X { a: 1, b: 0, c: "x".into() }));
+/// ```
+///
+/// These attributes cannot be specified together with [`#[display(opt)]`](#displayopt).
+///
+/// ## `#[display(kv)]`
+///
+/// By writing `#[display(kv)]` on a struct with named fields, each field is displayed as `name=value` and the pairs are joined with a space.
+/// The separator between pairs and the string between a name and a value can be changed with `#[display(kv(sep = "...", assign = "..."))]`.
+///
+/// `FromStr` accepts the pairs in any order.
+/// An error is returned if the input contains an unknown key or the same key more than once.
+/// A missing key is an error, unless the field has `#[from_str(default)]` or the struct has `#[from_str(default)]`.
+///
+/// ```rust
+/// use parse_display::{Display, FromStr};
+///
+/// #[derive(Display, FromStr, PartialEq, Debug)]
+/// #[display(kv)]
+/// struct X {
+///   a: u32,
+///   b: String,
+///   #[from_str(default)]
+///   c: bool,
+/// }
+/// let value = X { a: 1, b: "foo".into(), c: true };
+/// assert_eq!(value.to_string(), "a=1 b=foo c=true");
+/// assert_eq!("c=true b=foo a=1".parse(), Ok(value));
+/// assert_eq!("a=1 b=foo".parse(), Ok(X { a: 1, b: "foo".into(), c: false }));
+/// assert!("a=1 b=foo d=1".parse::<X>().is_err());
+/// ```
+///
+/// The names of the fields are converted with the style specified by [`#[display(style = "...")]`](#displaystyle--).
+/// Fields with [`#[display(opt)]`](#displayopt) are omitted when they are `None`, and become `None` when their key is missing. If `#[display(opt = "...")]` is specified, the key is output with that string instead.
+/// Similarly, fields with [`#[display(omit_if = ...)]` or `#[display(opt_empty)]`](#displayomit_if---displayopt_empty) are omitted when the condition holds, and become `Default::default()` when their key is missing.
+/// The format of a field cannot be specified, but [`#[display(with = ...)]`](#displaywith---from_strwith--) can be used.
+/// Since the input is split at `sep`, values containing `sep` cannot be parsed.
+///
+/// ### `#[from_str(ignore_unknown_keys)]`
+///
+/// By writing `#[from_str(ignore_unknown_keys)]` together with `#[display(kv)]`, unknown keys are ignored instead of being an error.
+///
+/// ```rust
+/// use parse_display::{Display, FromStr};
+///
+/// #[derive(Display, FromStr, PartialEq, Debug)]
+/// #[display(kv(sep = ", ", assign = ": "), style = "kebab-case")]
+/// #[from_str(ignore_unknown_keys)]
+/// struct X {
+///   user_id: u32,
+///   #[display(opt)]
+///   full_name: Option<String>,
+/// }
+/// let value = X { user_id: 1, full_name: None };
+/// assert_eq!(value.to_string(), "user-id: 1");
+/// assert_eq!("user-id: 1, unknown: 2".parse(), Ok(value));
+/// ```
+///
 /// ## `#[display(with = "...")]`, `#[from_str(with = "...")]`
 ///
 /// You can customize [`Display`] and [`FromStr`] processing for a field by specifying the values that implements [`DisplayFormat`] and [`FromStrFormat`].
@@ -756,6 +1011,263 @@ pub use from_str_regex::FromStrRegex;
 ///
 /// If `#[from_str(regex_infer)]` is specified for a type or variant rather than a field, this attribute is applied to all fields.
 ///
+/// The patterns for integer types only match values within the range of the type, so adjacent numeric fields without separators can be parsed.
+///
+/// ```rust
+/// use parse_display::FromStr;
+///
+/// #[derive(FromStr, PartialEq, Debug)]
+/// #[display("{a}{b}")]
+/// #[from_str(regex_infer)]
+/// struct X {
+///     a: u8,
+///     b: u8,
+/// }
+///
+/// assert_eq!("25599".parse(), Ok(X { a: 255, b: 99 }));
+/// ```
+///
+/// ## `#[from_str(strict)]`
+///
+/// By default, the derived `FromStr` accepts any string that the field type's `FromStr` accepts, even if `Display` never produces it. (e.g. `+42` and `042` for `{}`)
+///
+/// If `#[from_str(strict)]` is specified, fields of primitive numeric types only accept the string that `Display` produces with the format spec of the field, such as `{:08}`, `{:+}` and `{:.2}`.
+///
+/// ```rust
+/// use parse_display::{Display, FromStr};
+///
+/// #[derive(Display, FromStr, PartialEq, Debug)]
+/// #[display("{0:+},{1:04}")]
+/// #[from_str(strict)]
+/// struct X(i32, i32);
+///
+/// assert_eq!(X(1, -2).to_string(), "+1,-002");
+/// assert_eq!("+1,-002".parse(), Ok(X(1, -2)));
+/// assert!("1,-002".parse::<X>().is_err());
+/// assert!("+1,-0002".parse::<X>().is_err());
+/// ```
+///
+/// If `#[from_str(strict)]` is specified for a type or variant rather than a field, this attribute is applied to all fields.
+///
+/// ## `#[from_str(ignore_case)]`
+///
+/// Matches the strings in the format case-insensitively.
+/// `Display` still outputs the strings as specified by the format and [`#[display(style = "...")]`](#displaystyle--).
+///
+/// ```rust
+/// use parse_display::{Display, FromStr};
+///
+/// #[derive(Display, FromStr, PartialEq, Debug)]
+/// #[display(style = "lowercase")]
+/// #[from_str(ignore_case)]
+/// enum Level {
+///   Info,
+///   Warn,
+/// }
+///
+/// assert_eq!(Level::Info.to_string(), "info");
+/// assert_eq!("INFO".parse(), Ok(Level::Info));
+/// assert_eq!("Warn".parse(), Ok(Level::Warn));
+/// ```
+///
+/// If `#[from_str(ignore_case)]` is specified for an enum, this attribute is applied to all variants.
+/// When the regex is used for parsing, the whole regex, including the regex specified for fields, is matched case-insensitively.
+///
+/// ## `#[from_str(alias = "...")]`, `#[from_str(aliases = [...])]`
+///
+/// Specifies additional strings accepted by `FromStr` for a variant or a struct whose format is a fixed string.
+/// `Display` outputs only the string specified by the format.
+///
+/// ```rust
+/// use parse_display::{Display, FromStr};
+///
+/// #[derive(Display, FromStr, PartialEq, Debug)]
+/// #[display(style = "lowercase")]
+/// enum Level {
+///   #[from_str(alias = "warn")]
+///   Warning,
+///   #[from_str(aliases = ["err", "e"])]
+///   Error,
+/// }
+///
+/// assert_eq!(Level::Warning.to_string(), "warning");
+/// assert_eq!("warn".parse(), Ok(Level::Warning));
+/// assert_eq!("e".parse(), Ok(Level::Error));
+/// ```
+///
+/// `#[from_str(alias = "...")]` can be specified multiple times.
+///
+/// ## `#[from_str(other)]`
+///
+/// Specifies the variant used when the input does not match any other variant.
+/// The variant must have exactly one field, and the whole input is parsed into it with [`FromStr`](core::str::FromStr).
+/// `Display` outputs the field as is.
+///
+/// ```rust
+/// use parse_display::{Display, FromStr};
+///
+/// #[derive(Display, FromStr, PartialEq, Debug)]
+/// #[display(style = "UPPERCASE")]
+/// enum Method {
+///   Get,
+///   Post,
+///   #[from_str(other)]
+///   Other(String),
+/// }
+///
+/// assert_eq!("GET".parse(), Ok(Method::Get));
+/// assert_eq!("PATCH".parse(), Ok(Method::Other("PATCH".into())));
+/// assert_eq!(Method::Other("PATCH".into()).to_string(), "PATCH");
+/// ```
+///
+/// The variant is tried after all other variants, regardless of its position.
+///
+/// ## `#[from_str(any_order)]`
+///
+/// By writing `#[from_str(any_order)]` on a struct, the format is split at a delimiter into segments, and the segments of the input are accepted in any order.
+/// `Display` outputs the format as written.
+///
+/// The delimiter is specified as `#[from_str(any_order = "...")]`.
+/// If omitted, the leading symbols (characters other than alphanumeric and `_`) of the string between the first two placeholders are used as the delimiter.
+///
+/// ```rust
+/// use parse_display::{Display, FromStr};
+///
+/// #[derive(Display, FromStr, PartialEq, Debug)]
+/// #[display("x={x} y={y}")]
+/// #[from_str(any_order)]
+/// struct Point {
+///   x: i32,
+///   y: i32,
+/// }
+/// assert_eq!("x=1 y=2".parse(), Ok(Point { x: 1, y: 2 }));
+/// assert_eq!("y=2 x=1".parse(), Ok(Point { x: 1, y: 2 }));
+///
+/// #[derive(Display, FromStr, PartialEq, Debug)]
+/// #[display("width:{width}|height:{height}")]
+/// #[from_str(any_order = "|")]
+/// struct Size {
+///   width: u32,
+///   height: u32,
+/// }
+/// assert_eq!("height:2|width:1".parse(), Ok(Size { width: 1, height: 2 }));
+/// ```
+///
+/// The input is split at the delimiter, and each piece is matched with a segment that has not matched yet.
+/// If a field fails to parse, the pieces are matched with the other segments, and if all the ways fail, the error of the first way is returned.
+/// To bound the time on untrusted input, the search stops and returns the error of the first way after segments have matched pieces 1000 times in total.
+/// Therefore, the delimiter cannot appear inside the values.
+/// Segments that are not distinguishable by their literal text, such as `x=` and `y=` above, are distinguished only by whether their fields parse.
+///
+/// ```rust
+/// use parse_display::FromStr;
+///
+/// #[derive(FromStr, PartialEq, Debug)]
+/// #[display("{id} {name}")]
+/// #[from_str(any_order)]
+/// struct User {
+///     id: u32,
+///     name: String,
+/// }
+///
+/// assert_eq!("alice 1".parse(), Ok(User { id: 1, name: "alice".into() }));
+/// ```
+///
+/// The string before the first delimiter and after the last delimiter of the format belong to the first and last segment respectively.
+///
+/// ## `#[from_str(backtrack)]`
+///
+/// By default, if the input matches the format but a field fails to parse, parsing fails even if another way of splitting the input would succeed.
+///
+/// If `#[from_str(backtrack)]` is specified, the other ways of splitting the input at the fields are also tried until all fields are parsed.
+/// The maximum number of ways to try can be specified as `#[from_str(backtrack = 100)]`. If omitted, it is 1000.
+///
+/// ```rust
+/// use parse_display::FromStr;
+///
+/// #[derive(FromStr, PartialEq, Debug)]
+/// #[display("{a}{b}")]
+/// struct X {
+///   a: u32,
+///   b: String,
+/// }
+///
+/// // `a` matches "" and fails to parse.
+/// assert!("1a".parse::<X>().is_err());
+///
+/// #[derive(FromStr, PartialEq, Debug)]
+/// #[display("{a}{b}")]
+/// #[from_str(backtrack)]
+/// struct Y {
+///   a: u32,
+///   b: String,
+/// }
+///
+/// // `a` matches "1" and `b` matches "a" on retry.
+/// assert_eq!("1a".parse(), Ok(Y { a: 1, b: "a".into() }));
+/// ```
+///
+/// If all the ways fail, the error of the first way is returned.
+/// If `#[from_str(backtrack)]` is specified for an enum, it is applied to all variants.
+///
+/// ## `#[from_str(prefix)]`
+///
+/// By writing `#[from_str(prefix)]` on a struct or enum, [`FromStrPrefix`] is also implemented, which parses a value from the beginning of a string and returns the rest.
+///
+/// The beginning of the string is matched with the format, and the fields are matched with the regexes of their types as with [`#[from_str(regex_infer)]`](#from_strregex_infer).
+/// Therefore, the field types must implement [`FromStrRegex`].
+/// This does not change the regexes used by [`FromStr`].
+/// A field of a type that matches any string, such as `String`, matches the shortest string, so use `#[from_str(regex = "...")]` to determine its end if it is at the end of the format.
+///
+/// ```rust
+/// use parse_display::{FromStr, FromStrPrefix};
+///
+/// #[derive(FromStr, PartialEq, Debug)]
+/// #[display("{x}:{y}")]
+/// #[from_str(prefix)]
+/// struct Point {
+///   x: u32,
+///   y: u32,
+/// }
+/// assert_eq!(Point::parse_prefix("1:2,3:4"), Ok((Point { x: 1, y: 2 }, ",3:4")));
+/// ```
+///
+/// `FromStrPrefix` is also implemented for primitive types such as integers, floats, `bool` and `char`.
+///
+/// ## `#[from_str(find)]`
+///
+/// By writing `#[from_str(find)]` on a struct or enum, [`FromStrFind`] is also implemented, which finds the values in a larger string.
+///
+/// [`FromStrFind::find_iter`] returns an iterator over the byte range of each match and the result of parsing it, and [`FromStrFind::find`] returns the first one.
+/// [`FromStrFind::captures`] returns the first match as [`FindCaptures`], which also has the byte ranges of the fields.
+///
+/// The values are matched with the regex used by [`FromStr`] without the anchors, so `find` accepts the same strings as `FromStr`.
+/// Since the fields are matched with `(?s:.*?)` by default, a field at the end of the format matches the shortest string.
+/// Use `#[from_str(regex_infer)]` or `#[from_str(regex = "...")]` to determine where the fields end.
+///
+/// ```rust
+/// use parse_display::{FromStr, FromStrFind};
+///
+/// #[derive(FromStr, PartialEq, Debug)]
+/// #[display("{user}@{host}")]
+/// #[from_str(find)]
+/// struct Address {
+///   #[from_str(regex = "[a-z]+")]
+///   user: String,
+///   #[from_str(regex = "[a-z.]+")]
+///   host: String,
+/// }
+/// let text = "from alice@example.com to bob@localhost";
+/// let users: Vec<_> = Address::find_iter(text)
+///     .map(|(_, a)| a.unwrap().user)
+///     .collect();
+/// assert_eq!(users, ["alice", "bob"]);
+///
+/// let c = Address::captures(text).unwrap();
+/// assert_eq!(c.range(), 5..22);
+/// assert_eq!(c.field("host"), Some("example.com"));
+/// ```
+///
 /// ## `#[from_str(new = ...)]`
 ///
 /// If `#[from_str(new = ...)]` is specified, the value will be initialized with the specified expression instead of the constructor.
@@ -808,6 +1320,52 @@ pub use from_str_regex::FromStrRegex;
 /// assert_eq!("0".parse::<MyNonZeroUSize>().is_err(), true);
 /// ```
 ///
+/// ## `#[from_str(error = ...)]`
+///
+/// Specifies the type of [`FromStr::Err`](core::str::FromStr::Err). The default is [`ParseError`].
+///
+/// The type must implement `From<ParseError>`, which is used when the input does not match the format,
+/// and `From<E>` for the error type `E` of each field's parser.
+///
+/// ```rust
+/// use parse_display::{FromStr, ParseError};
+/// use std::num::ParseIntError;
+///
+/// #[derive(Debug, PartialEq)]
+/// enum MyError {
+///     Format,
+///     Int(ParseIntError),
+/// }
+/// impl From<ParseError> for MyError {
+///     fn from(_: ParseError) -> Self {
+///         MyError::Format
+///     }
+/// }
+/// impl From<ParseIntError> for MyError {
+///     fn from(e: ParseIntError) -> Self {
+///         MyError::Int(e)
+///     }
+/// }
+///
+/// #[derive(FromStr, Debug, PartialEq)]
+/// #[display("{a},{b}")]
+/// #[from_str(error = MyError)]
+/// struct X {
+///     a: u32,
+///     b: u32,
+/// }
+///
+/// assert_eq!("1,2".parse(), Ok(X { a: 1, b: 2 }));
+/// assert!(matches!("1,x".parse::<X>(), Err(MyError::Int(_))));
+/// assert_eq!("1".parse::<X>(), Err(MyError::Format));
+/// ```
+///
+/// If [`#[from_str(new = ...)]`](#from_strnew--) is also specified, the error returned by the expression is converted with `From` and returned.
+///
+/// The error of a field's parser is converted with `From<E>` as it is.
+/// Therefore, unlike [`ParseError`], the error does not carry the name of the field and variant or the byte range of the input where the error occurred.
+/// If you need them, use the default [`ParseError`], which keeps the error of the field's parser as [`Error::source`](core::error::Error::source).
+///
 /// ## `#[from_str(ignore)]`
 ///
 /// Specifying this attribute for a variant will not generate `FromStr` implementation for that variant.
@@ -893,16 +1451,285 @@ pub use parse_display_derive::Display;
 /// See [`#[derive(Display)]`](derive@Display) for details.
 pub use parse_display_derive::FromStr;
 
+/// Parses a string with a format and returns the values of the placeholders.
+///
+/// `scan!(input, "format")` parses `input` in the same way as [`#[derive(FromStr)]`](derive@FromStr) parses a struct,
+/// without declaring the struct.
+/// `input` is an expression that implements `AsRef<str>`, and the result is `Result<_, ParseError>`.
+///
+/// The value is a tuple of the values of the placeholders in order of appearance,
+/// or the value itself if the format has only one placeholder.
+/// The type of a value can be written after `:` in the placeholder (e.g. `{:u32}`, `{x:u32}`) or inferred from the usage.
+/// Placeholders with the same name share the same value, which is parsed from the first occurrence.
+/// Unlike `#[display("...")]`, format specs such as `{:x}` cannot be used.
+///
+/// ```
+/// use parse_display::scan;
+///
+/// let (a, b): (u32, String) = scan!("10-abc", "{}-{}")?;
+/// assert_eq!((a, b), (10, "abc".to_string()));
+///
+/// let (x, y) = scan!("1,2", "{x:u32},{y:u32}")?;
+/// assert_eq!((x, y), (1, 2));
+///
+/// let n: u8 = scan!(String::from("n=5"), "n={}")?;
+/// assert_eq!(n, 5);
+/// # Ok::<(), parse_display::ParseError>(())
+/// ```
+pub use parse_display_derive::scan;
+
 /// Error type used in the implementation of [`FromStr`] generated by `#[derive(FromStr)]`
-#[derive(Debug, Eq, PartialEq)]
-pub struct ParseError(&'static str);
+///
+/// In addition to the message, the error can carry the name of the field and variant that failed to parse,
+/// and the byte range of the input that was given to the field.
+///
+/// With the `alloc` feature, the error returned by the field's parser is available from [`Error::source`](core::error::Error::source)
+/// if it implements [`Error`](core::error::Error) + [`Send`] + [`Sync`].
+///
+/// If another error type is specified with [`#[from_str(error = ...)]`](macro@Display#from_strerror--),
+/// the errors of the fields are converted to it directly and this information is not available.
+///
+/// ```
+/// use parse_display::FromStr;
+///
+/// #[derive(FromStr, Debug)]
+/// #[display("{host}:{port}")]
+/// struct Addr {
+///     host: String,
+///     port: u16,
+/// }
+///
+/// let e = "localhost:http".parse::<Addr>().unwrap_err();
+/// assert_eq!(e.field(), Some("port"));
+/// assert_eq!(e.span(), Some(10..14));
+/// assert_eq!(e.to_string(), "field `port` (bytes 10..14): parse failed.");
+///
+/// let source = std::error::Error::source(&e).unwrap();
+/// assert_eq!(source.to_string(), "invalid digit found in string");
+/// ```
+///
+/// For enums where all variants are parsed from fixed strings, the error carries the list of accepted strings.
+/// With the `alloc` feature, it also carries the input and the accepted string closest to it.
+///
+/// ```
+/// use parse_display::FromStr;
+///
+/// #[derive(FromStr, Debug)]
+/// #[display(style = "lowercase")]
+/// enum Level {
+///     Debug,
+///     Info,
+///     Warn,
+/// }
+///
+/// let e = "infi".parse::<Level>().unwrap_err();
+/// assert_eq!(e.expected(), ["debug", "info", "warn"]);
+/// assert_eq!(e.suggestion(), Some("info"));
+/// assert_eq!(
+///     e.to_string(),
+///     "expected one of `debug`, `info`, `warn`, got `infi` (did you mean `info`?)"
+/// );
+/// ```
+#[derive(Debug)]
+pub struct ParseError {
+    message: &'static str,
+    field: Option<&'static str>,
+    variant: Option<&'static str>,
+    span: Option<Range<usize>>,
+    input_len: Option<usize>,
+    expected: &'static [&'static str],
+    // The rarely used details (input, suggestion and source) are boxed; the other fields are inline.
+    #[cfg(feature = "alloc")]
+    details: Option<Box<ParseErrorDetails>>,
+}
+
+#[cfg(feature = "alloc")]
+#[derive(Debug, Default)]
+struct ParseErrorDetails {
+    input: Option<String>,
+    suggestion: Option<&'static str>,
+    source: Option<Box<dyn core::error::Error + Send + Sync>>,
+}
 impl ParseError {
     pub fn with_message(message: &'static str) -> Self {
-        Self(message)
+        Self {
+            message,
+            field: None,
+            variant: None,
+            span: None,
+            input_len: None,
+            expected: &[],
+            #[cfg(feature = "alloc")]
+            details: None,
+        }
     }
     pub fn new() -> Self {
         Self::with_message("parse failed.")
     }
+
+    /// Sets the name of the field that failed to parse.
+    pub fn with_field(mut self, field: &'static str) -> Self {
+        self.field = Some(field);
+        self
+    }
+
+    /// Sets the name of the variant that failed to parse.
+    pub fn with_variant(mut self, variant: &'static str) -> Self {
+        self.variant = Some(variant);
+        self
+    }
+
+    /// Sets the byte range of the input where the error occurred and the length of the whole input.
+    pub fn with_span(mut self, span: Range<usize>, input_len: usize) -> Self {
+        self.span = Some(span);
+        self.input_len = Some(input_len);
+        self
+    }
+
+    /// Sets the list of accepted strings and the input that did not match any of them.
+    ///
+    /// With the `alloc` feature, the input is kept and the accepted string closest to it is selected as a suggestion.
+    pub fn with_expected(mut self, expected: &'static [&'static str], input: &str) -> Self {
+        self.expected = expected;
+        #[cfg(feature = "alloc")]
+        {
+            let details = self.details_mut();
+            details.input = Some(input.into());
+            details.suggestion = suggest(expected, input);
+        }
+        #[cfg(not(feature = "alloc"))]
+        let _ = input;
+        self
+    }
+
+    /// Sets the error that caused this error.
+    #[cfg(feature = "alloc")]
+    pub fn with_source(
+        mut self,
+        source: impl Into<Box<dyn core::error::Error + Send + Sync>>,
+    ) -> Self {
+        self.details_mut().source = Some(source.into());
+        self
+    }
+    #[cfg(feature = "alloc")]
+    fn details_mut(&mut self) -> &mut ParseErrorDetails {
+        self.details.get_or_insert_with(Default::default)
+    }
+
+    /// Returns the error message.
+    pub fn message(&self) -> &'static str {
+        self.message
+    }
+
+    /// Returns the name of the field that failed to parse.
+    ///
+    /// For tuple structs and tuple variants, the index of the field is returned. (e.g. `"0"`)
+    pub fn field(&self) -> Option<&'static str> {
+        self.field
+    }
+
+    /// Returns the name of the variant that failed to parse.
+    pub fn variant(&self) -> Option<&'static str> {
+        self.variant
+    }
+
+    /// Returns the byte range of the input where the error occurred.
+    pub fn span(&self) -> Option<Range<usize>> {
+        self.span.clone()
+    }
+
+    /// Returns the length of the input that was being parsed when the error occurred.
+    pub fn input_len(&self) -> Option<usize> {
+        self.input_len
+    }
+
+    /// Returns the list of accepted strings.
+    ///
+    /// Empty if the accepted strings are not known.
+    pub fn expected(&self) -> &'static [&'static str] {
+        self.expected
+    }
+
+    /// Returns the input that did not match any of [`expected`](Self::expected).
+    #[cfg(feature = "alloc")]
+    pub fn input(&self) -> Option<&str> {
+        self.details.as_ref()?.input.as_deref()
+    }
+
+    /// Returns the string in [`expected`](Self::expected) closest to the input, if any is close enough.
+    #[cfg(feature = "alloc")]
+    pub fn suggestion(&self) -> Option<&'static str> {
+        self.details.as_ref()?.suggestion
+    }
+
+    fn fmt_expected(&self, f: &mut Formatter) -> Result {
+        if let [expected] = self.expected {
+            write!(f, "expected `{expected}`")?;
+        } else {
+            write!(f, "expected one of ")?;
+            for (i, expected) in self.expected.iter().enumerate() {
+                if i != 0 {
+                    write!(f, ", ")?;
+                }
+                write!(f, "`{expected}`")?;
+            }
+        }
+        #[cfg(feature = "alloc")]
+        {
+            if let Some(input) = self.input() {
+                write!(f, ", got `{input}`")?;
+            }
+            if let Some(suggestion) = self.suggestion() {
+                write!(f, " (did you mean `{suggestion}`?)")?;
+            }
+        }
+        Ok(())
+    }
+
+    #[cfg(feature = "alloc")]
+    fn source_error(&self) -> Option<&(dyn core::error::Error + Send + Sync + 'static)> {
+        self.details.as_ref()?.source.as_deref()
+    }
+
+    #[cfg(feature = "alloc")]
+    fn eq_input(&self, other: &Self) -> bool {
+        self.input() == other.input()
+    }
+    #[cfg(not(feature = "alloc"))]
+    fn eq_input(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+#[cfg(feature = "alloc")]
+fn suggest(expected: &[&'static str], input: &str) -> Option<&'static str> {
+    let max_distance = input.chars().count().max(3) / 3;
+    expected
+        .iter()
+        .map(|&s| (edit_distance(s, input), s))
+        .filter(|&(d, s)| d <= max_distance && d < s.chars().count())
+        .min_by_key(|&(d, _)| d)
+        .map(|(_, s)| s)
+}
+
+#[cfg(feature = "alloc")]
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().flat_map(char::to_lowercase).collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().flat_map(char::to_lowercase).enumerate() {
+        let mut prev = row[0];
+        row[0] = i + 1;
+        for (j, &cb) in b.iter().enumerate() {
+            let cur = row[j + 1];
+            row[j + 1] = if ca == cb {
+                prev
+            } else {
+                prev.min(cur).min(row[j]) + 1
+            };
+            prev = cur;
+        }
+    }
+    row[b.len()]
 }
 impl Default for ParseError {
     fn default() -> Self {
@@ -910,14 +1737,52 @@ impl Default for ParseError {
     }
 }
 
+/// The source error is not compared.
+impl PartialEq for ParseError {
+    fn eq(&self, other: &Self) -> bool {
+        self.message == other.message
+            && self.field == other.field
+            && self.variant == other.variant
+            && self.span == other.span
+            && self.input_len == other.input_len
+            && self.expected == other.expected
+            && self.eq_input(other)
+    }
+}
+impl Eq for ParseError {}
+
 impl Display for ParseError {
     fn fmt(&self, f: &mut Formatter) -> Result {
-        write!(f, "{}", self.0)
+        let mut sep = "";
+        if let Some(variant) = self.variant {
+            write!(f, "variant `{variant}`")?;
+            sep = ", ";
+        }
+        if let Some(field) = self.field {
+            write!(f, "{sep}field `{field}`")?;
+            sep = " ";
+        }
+        if let Some(span) = &self.span {
+            write!(f, "{sep}(bytes {}..{})", span.start, span.end)?;
+            sep = " ";
+        }
+        if !sep.is_empty() {
+            write!(f, ": ")?;
+        }
+        if !self.expected.is_empty() {
+            return self.fmt_expected(f);
+        }
+        write!(f, "{}", self.message)
     }
 }
 impl core::error::Error for ParseError {
     fn description(&self) -> &str {
-        self.0
+        self.message
+    }
+
+    #[cfg(feature = "alloc")]
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        Some(self.source_error()?)
     }
 }
 
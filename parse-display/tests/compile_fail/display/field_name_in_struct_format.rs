@@ -0,0 +1,9 @@
+use parse_display::Display;
+
+#[derive(Display)]
+#[display("{#name}")]
+struct X {
+    a: u32,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use parse_display::Display;
+
+#[derive(Display)]
+#[display(kv)]
+struct X {
+    #[display("<{}>")]
+    a: u32,
+}
+
+fn main() {}
@@ -0,0 +1,7 @@
+use parse_display::Display;
+
+#[derive(Display)]
+#[display(kv)]
+struct X(u32, u32);
+
+fn main() {}
@@ -0,0 +1,10 @@
+use parse_display::Display;
+
+#[derive(Display)]
+#[display("{a}")]
+struct X {
+    #[display(opt, opt_empty)]
+    a: Option<u32>,
+}
+
+fn main() {}
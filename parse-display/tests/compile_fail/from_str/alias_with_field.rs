@@ -0,0 +1,10 @@
+use parse_display::FromStr;
+
+#[derive(FromStr)]
+enum TestEnum {
+    #[display("a{0}")]
+    #[from_str(alias = "b")]
+    A(u32),
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use parse_display::FromStr;
+
+#[derive(FromStr)]
+#[from_str(any_order)]
+enum X {
+    A,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use parse_display::FromStr;
+
+#[derive(FromStr)]
+#[display("{a}")]
+struct X {
+    #[from_str(backtrack)]
+    a: u32,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use parse_display::FromStr;
+
+#[derive(FromStr)]
+#[display("{a}{b}")]
+#[from_str(backtrack = 0)]
+struct X {
+    a: u32,
+    b: u32,
+}
+
+fn main() {}
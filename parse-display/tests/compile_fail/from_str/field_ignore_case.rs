@@ -0,0 +1,7 @@
+use parse_display::FromStr;
+
+#[derive(FromStr)]
+#[display("{0}")]
+struct TestStruct(#[from_str(ignore_case)] String);
+
+fn main() {}
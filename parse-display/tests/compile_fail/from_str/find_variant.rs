@@ -0,0 +1,9 @@
+use parse_display::FromStr;
+
+#[derive(FromStr)]
+enum X {
+    #[from_str(find)]
+    A,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use parse_display::FromStr;
+
+#[derive(FromStr)]
+#[display("{a}")]
+#[from_str(ignore_unknown_keys)]
+struct X {
+    a: u32,
+}
+
+fn main() {}
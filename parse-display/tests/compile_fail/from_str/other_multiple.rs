@@ -0,0 +1,11 @@
+use parse_display::FromStr;
+
+#[derive(FromStr)]
+enum TestEnum {
+    #[from_str(other)]
+    A(String),
+    #[from_str(other)]
+    B(String),
+}
+
+fn main() {}
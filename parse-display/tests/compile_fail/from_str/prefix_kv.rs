@@ -0,0 +1,10 @@
+use parse_display::FromStr;
+
+#[derive(FromStr)]
+#[display(kv)]
+#[from_str(prefix)]
+struct X {
+    a: u32,
+}
+
+fn main() {}
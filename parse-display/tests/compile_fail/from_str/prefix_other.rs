@@ -0,0 +1,11 @@
+use parse_display::FromStr;
+
+#[derive(FromStr)]
+#[from_str(prefix)]
+enum X {
+    A,
+    #[from_str(other)]
+    Other(String),
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use parse_display::FromStr;
+
+#[derive(FromStr)]
+enum TestEnum {
+    #[from_str(error = std::fmt::Error)]
+    A,
+}
+
+fn main() {}
@@ -0,0 +1,5 @@
+use parse_display::scan;
+
+fn main() {
+    let _: Result<u32, _> = scan!("1", "{a.b}");
+}
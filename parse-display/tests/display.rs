@@ -453,6 +453,322 @@ fn display_enum_upper_title_case_head() {
     assert_display(TestEnum::_Xxx, "Xxx");
 }
 
+#[test]
+fn display_enum_flat_case() {
+    #[derive(Display)]
+    #[display(style = "flatcase")]
+    enum TestEnum {
+        AbcDef,
+        XyzXyz,
+        Abc1,
+        Abc1Abc2,
+        Xxx1xxx,
+        _Xxx,
+    }
+    assert_display(TestEnum::AbcDef, "abcdef");
+    assert_display(TestEnum::XyzXyz, "xyzxyz");
+    assert_display(TestEnum::Abc1, "abc1");
+    assert_display(TestEnum::Abc1Abc2, "abc1abc2");
+    assert_display(TestEnum::Xxx1xxx, "xxx1xxx");
+    assert_display(TestEnum::_Xxx, "xxx");
+}
+
+#[test]
+fn display_enum_upper_flat_case() {
+    #[derive(Display)]
+    #[display(style = "UPPERFLATCASE")]
+    enum TestEnum {
+        AbcDef,
+        XyzXyz,
+        Abc1,
+        Abc1Abc2,
+        Xxx1xxx,
+        _Xxx,
+    }
+    assert_display(TestEnum::AbcDef, "ABCDEF");
+    assert_display(TestEnum::XyzXyz, "XYZXYZ");
+    assert_display(TestEnum::Abc1, "ABC1");
+    assert_display(TestEnum::Abc1Abc2, "ABC1ABC2");
+    assert_display(TestEnum::Xxx1xxx, "XXX1XXX");
+    assert_display(TestEnum::_Xxx, "XXX");
+}
+
+#[test]
+fn display_enum_cobol_case() {
+    #[derive(Display)]
+    #[display(style = "COBOL-CASE")]
+    enum TestEnum {
+        AbcDef,
+        XyzXyz,
+        Abc1,
+        Abc1Abc2,
+        Xxx1xxx,
+        _Xxx,
+    }
+    assert_display(TestEnum::AbcDef, "ABC-DEF");
+    assert_display(TestEnum::XyzXyz, "XYZ-XYZ");
+    assert_display(TestEnum::Abc1, "ABC1");
+    assert_display(TestEnum::Abc1Abc2, "ABC1-ABC2");
+    assert_display(TestEnum::Xxx1xxx, "XXX1XXX");
+    assert_display(TestEnum::_Xxx, "XXX");
+}
+
+#[test]
+fn display_enum_train_case() {
+    #[derive(Display)]
+    #[display(style = "Train-Case")]
+    enum TestEnum {
+        AbcDef,
+        XyzXyz,
+        Abc1,
+        Abc1Abc2,
+        Xxx1xxx,
+        _Xxx,
+    }
+    assert_display(TestEnum::AbcDef, "Abc-Def");
+    assert_display(TestEnum::XyzXyz, "Xyz-Xyz");
+    assert_display(TestEnum::Abc1, "Abc1");
+    assert_display(TestEnum::Abc1Abc2, "Abc1-Abc2");
+    assert_display(TestEnum::Xxx1xxx, "Xxx1xxx");
+    assert_display(TestEnum::_Xxx, "Xxx");
+}
+
+#[test]
+fn display_enum_dot_case() {
+    #[derive(Display)]
+    #[display(style = "dot.case")]
+    enum TestEnum {
+        AbcDef,
+        XyzXyz,
+        Abc1,
+        Abc1Abc2,
+        Xxx1xxx,
+        _Xxx,
+    }
+    assert_display(TestEnum::AbcDef, "abc.def");
+    assert_display(TestEnum::XyzXyz, "xyz.xyz");
+    assert_display(TestEnum::Abc1, "abc1");
+    assert_display(TestEnum::Abc1Abc2, "abc1.abc2");
+    assert_display(TestEnum::Xxx1xxx, "xxx1xxx");
+    assert_display(TestEnum::_Xxx, "xxx");
+}
+
+#[test]
+fn display_enum_path_case() {
+    #[derive(Display)]
+    #[display(style = "path/case")]
+    enum TestEnum {
+        AbcDef,
+        XyzXyz,
+        Abc1,
+        Abc1Abc2,
+        Xxx1xxx,
+        _Xxx,
+    }
+    assert_display(TestEnum::AbcDef, "abc/def");
+    assert_display(TestEnum::XyzXyz, "xyz/xyz");
+    assert_display(TestEnum::Abc1, "abc1");
+    assert_display(TestEnum::Abc1Abc2, "abc1/abc2");
+    assert_display(TestEnum::Xxx1xxx, "xxx1xxx");
+    assert_display(TestEnum::_Xxx, "xxx");
+}
+
+#[test]
+fn display_enum_custom_lower() {
+    #[derive(Display)]
+    #[display(style = "{lower}:{sep=::}")]
+    enum TestEnum {
+        AbcDef,
+        XyzXyz,
+        Abc1,
+        Abc1Abc2,
+        Xxx1xxx,
+        _Xxx,
+    }
+    assert_display(TestEnum::AbcDef, "abc::def");
+    assert_display(TestEnum::XyzXyz, "xyz::xyz");
+    assert_display(TestEnum::Abc1, "abc1");
+    assert_display(TestEnum::Abc1Abc2, "abc1::abc2");
+    assert_display(TestEnum::Xxx1xxx, "xxx1xxx");
+    assert_display(TestEnum::_Xxx, "xxx");
+}
+
+#[test]
+fn display_enum_custom_upper() {
+    #[derive(Display)]
+    #[display(style = "{upper}:{sep=.}")]
+    enum TestEnum {
+        AbcDef,
+        XyzXyz,
+        Abc1,
+        Abc1Abc2,
+        Xxx1xxx,
+        _Xxx,
+    }
+    assert_display(TestEnum::AbcDef, "ABC.DEF");
+    assert_display(TestEnum::XyzXyz, "XYZ.XYZ");
+    assert_display(TestEnum::Abc1, "ABC1");
+    assert_display(TestEnum::Abc1Abc2, "ABC1.ABC2");
+    assert_display(TestEnum::Xxx1xxx, "XXX1XXX");
+    assert_display(TestEnum::_Xxx, "XXX");
+}
+
+#[test]
+fn display_enum_custom_title() {
+    #[derive(Display)]
+    #[display(style = "{title}:{sep=_}")]
+    enum TestEnum {
+        AbcDef,
+        XyzXyz,
+        Abc1,
+        Abc1Abc2,
+        Xxx1xxx,
+        _Xxx,
+    }
+    assert_display(TestEnum::AbcDef, "Abc_Def");
+    assert_display(TestEnum::XyzXyz, "Xyz_Xyz");
+    assert_display(TestEnum::Abc1, "Abc1");
+    assert_display(TestEnum::Abc1Abc2, "Abc1_Abc2");
+    assert_display(TestEnum::Xxx1xxx, "Xxx1xxx");
+    assert_display(TestEnum::_Xxx, "Xxx");
+}
+
+#[test]
+fn display_enum_custom_camel() {
+    #[derive(Display)]
+    #[display(style = "{camel}:{sep=}")]
+    enum TestEnum {
+        AbcDef,
+        XyzXyz,
+        Abc1,
+        Abc1Abc2,
+        Xxx1xxx,
+        _Xxx,
+    }
+    assert_display(TestEnum::AbcDef, "abcDef");
+    assert_display(TestEnum::XyzXyz, "xyzXyz");
+    assert_display(TestEnum::Abc1, "abc1");
+    assert_display(TestEnum::Abc1Abc2, "abc1Abc2");
+    assert_display(TestEnum::Xxx1xxx, "xxx1xxx");
+    assert_display(TestEnum::_Xxx, "xxx");
+}
+
+#[test]
+fn display_struct_field_name() {
+    #[derive(Display)]
+    #[display("{user_id}, {full_name}", style = "kebab-case")]
+    struct TestStruct {
+        #[display("{#name}={}")]
+        user_id: u32,
+        #[display("{#name}={}")]
+        full_name: &'static str,
+    }
+    assert_display(
+        TestStruct {
+            user_id: 1,
+            full_name: "abc",
+        },
+        "user-id=1, full-name=abc",
+    );
+}
+
+#[test]
+fn display_struct_field_name_no_style() {
+    #[derive(Display)]
+    #[display("{user_id}")]
+    struct TestStruct {
+        #[display("{#name}={}")]
+        user_id: u32,
+    }
+    assert_display(TestStruct { user_id: 1 }, "user_id=1");
+}
+
+#[test]
+fn display_enum_field_name() {
+    #[derive(Display)]
+    #[display(style = "SNAKE_CASE")]
+    enum TestEnum {
+        #[display("{user_id}")]
+        A {
+            #[display("{#name}:{}")]
+            user_id: u32,
+        },
+    }
+    assert_display(TestEnum::A { user_id: 5 }, "USER_ID:5");
+}
+
+#[test]
+fn display_struct_kv() {
+    #[derive(Display)]
+    #[display(kv)]
+    struct TestStruct {
+        a: u32,
+        b: &'static str,
+        c: bool,
+    }
+    assert_display(
+        TestStruct {
+            a: 1,
+            b: "foo",
+            c: true,
+        },
+        "a=1 b=foo c=true",
+    );
+}
+
+#[test]
+fn display_struct_kv_sep_assign_style() {
+    struct ToLowerHex;
+    impl parse_display::DisplayFormat<u32> for ToLowerHex {
+        fn write(&self, f: &mut core::fmt::Formatter, value: &u32) -> core::fmt::Result {
+            LowerHex::fmt(value, f)
+        }
+    }
+    #[derive(Display)]
+    #[display(kv(sep = ", ", assign = ": "), style = "kebab-case")]
+    struct TestStruct {
+        user_id: u32,
+        #[display(with = ToLowerHex)]
+        flags: u32,
+    }
+    assert_display(
+        TestStruct {
+            user_id: 1,
+            flags: 255,
+        },
+        "user-id: 1, flags: ff",
+    );
+}
+
+#[test]
+fn display_struct_kv_opt() {
+    #[derive(Display)]
+    #[display(kv)]
+    struct TestStruct {
+        #[display(opt)]
+        a: Option<u32>,
+        b: u32,
+        #[display(opt)]
+        c: Option<u32>,
+    }
+    assert_display(
+        TestStruct {
+            a: Some(1),
+            b: 2,
+            c: Some(3),
+        },
+        "a=1 b=2 c=3",
+    );
+    assert_display(
+        TestStruct {
+            a: None,
+            b: 2,
+            c: None,
+        },
+        "b=2",
+    );
+}
+
 #[test]
 fn display_enum_lower_case() {
     #[derive(Display)]
@@ -1206,6 +1522,82 @@ fn opt_generics() {
     assert_display(X { a: None::<u8> }, "");
 }
 
+#[test]
+fn opt_none_value() {
+    #[derive(Display)]
+    #[display("{a},{b}")]
+    struct X {
+        #[display(opt = "-")]
+        a: Option<u8>,
+        #[display("b={}", opt = "null")]
+        b: Option<u8>,
+    }
+    assert_display(
+        X {
+            a: Some(10),
+            b: Some(20),
+        },
+        "10,b=20",
+    );
+    assert_display(X { a: None, b: None }, "-,null");
+}
+
+#[test]
+fn display_struct_kv_opt_none_value() {
+    #[derive(Display)]
+    #[display(kv)]
+    struct X {
+        #[display(opt = "-")]
+        a: Option<u32>,
+        b: u32,
+    }
+    assert_display(X { a: Some(1), b: 2 }, "a=1 b=2");
+    assert_display(X { a: None, b: 2 }, "a=- b=2");
+}
+
+#[test]
+fn opt_empty() {
+    #[derive(Display)]
+    #[display("{a}{b}")]
+    struct X {
+        a: u32,
+        #[display(" b={}", opt_empty)]
+        b: &'static str,
+    }
+    assert_display(X { a: 1, b: "x" }, "1 b=x");
+    assert_display(X { a: 1, b: "" }, "1");
+}
+
+#[test]
+fn omit_if() {
+    fn is_zero(value: &u32) -> bool {
+        *value == 0
+    }
+
+    #[derive(Display)]
+    #[display("{a}{b}")]
+    struct X {
+        a: u32,
+        #[display(" b={}", omit_if = is_zero)]
+        b: u32,
+    }
+    assert_display(X { a: 1, b: 2 }, "1 b=2");
+    assert_display(X { a: 1, b: 0 }, "1");
+}
+
+#[test]
+fn display_struct_kv_omit() {
+    #[derive(Display)]
+    #[display(kv)]
+    struct X {
+        #[display(opt_empty)]
+        a: &'static str,
+        b: u32,
+    }
+    assert_display(X { a: "x", b: 2 }, "a=x b=2");
+    assert_display(X { a: "", b: 2 }, "b=2");
+}
+
 #[track_caller]
 fn assert_display<T: core::fmt::Display>(value: T, display: &str) {
     let value_display = format!("{value}");
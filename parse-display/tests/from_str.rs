@@ -2,8 +2,11 @@
 #![deny(clippy::pattern_type_mismatch)]
 
 use parse_display::*;
+use std::convert::Infallible;
+use std::error::Error;
 use std::fmt::Debug;
 use std::fmt::Display;
+use std::num::ParseIntError;
 use std::str::FromStr;
 
 #[test]
@@ -1281,6 +1284,29 @@ fn regex_infer_all() {
     );
 }
 
+#[test]
+fn regex_infer_adjacent_numbers() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display("{a}{b}")]
+    #[from_str(regex_infer)]
+    struct X {
+        a: u8,
+        b: u8,
+    }
+    assert_from_str("25599", X { a: 255, b: 99 });
+    assert_from_str("2569", X { a: 25, b: 69 });
+    assert_from_str_err::<X>("2569999");
+
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display("{a}{b}")]
+    #[from_str(regex_infer)]
+    struct Y {
+        a: i8,
+        b: u16,
+    }
+    assert_from_str("-12865535", Y { a: -128, b: 65535 });
+}
+
 #[test]
 fn from_str_regex_struct() {
     #[derive(FromStr, Debug, Eq, PartialEq)]
@@ -1439,6 +1465,1761 @@ fn from_str_opt_generic() {
     assert_from_str("", X { a: None::<u32> });
 }
 
+#[test]
+fn from_str_regex_simple_format() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display("[{a},{b}]")]
+    struct X {
+        a: u32,
+        b: u32,
+    }
+    let re = regex::Regex::new(&format!("^(?:{})$", X::from_str_regex())).unwrap();
+    assert!(re.is_match("[1,2]"));
+    assert!(re.is_match("[a,b,c]"));
+    assert!(!re.is_match("[1;2]"));
+
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    enum Y {
+        #[display("a({0})")]
+        A(u32),
+        B,
+    }
+    let re = regex::Regex::new(&format!("^(?:{})$", Y::from_str_regex())).unwrap();
+    assert!(re.is_match("a(1)"));
+    assert!(re.is_match("B"));
+    assert!(!re.is_match("a(1"));
+}
+
+#[test]
+fn parse_error_field() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display("{a},{b}")]
+    struct X {
+        a: u32,
+        b: u32,
+    }
+    let e = "10,abc".parse::<X>().unwrap_err();
+    assert_eq!(e.field(), Some("b"));
+    assert_eq!(e.variant(), None);
+    assert_eq!(e.span(), Some(3..6));
+    assert_eq!(e.input_len(), Some(6));
+    assert_eq!(e.to_string(), "field `b` (bytes 3..6): parse failed.");
+}
+
+#[test]
+fn parse_error_tuple_field() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display("{0}-{1}")]
+    struct X(u32, u32);
+    let e = "x-10".parse::<X>().unwrap_err();
+    assert_eq!(e.field(), Some("0"));
+    assert_eq!(e.span(), Some(0..1));
+}
+
+#[test]
+fn parse_error_deep_field() {
+    #[derive(Debug, Default, Eq, PartialEq)]
+    struct X {
+        a: u32,
+    }
+
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display("_{x.a}")]
+    struct Y {
+        #[from_str(default)]
+        x: X,
+    }
+    let e = "_z".parse::<Y>().unwrap_err();
+    assert_eq!(e.field(), Some("x.a"));
+    assert_eq!(e.span(), Some(1..2));
+    assert!(e.source().unwrap().is::<ParseIntError>());
+}
+
+#[test]
+fn parse_error_variant() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    enum X {
+        #[display("a={0}")]
+        A(u32),
+        #[display("b={0}")]
+        B(u32),
+    }
+    let e = "b=x".parse::<X>().unwrap_err();
+    assert_eq!(e.variant(), Some("B"));
+    assert_eq!(e.field(), Some("0"));
+    assert_eq!(e.span(), Some(2..3));
+    assert_eq!(
+        e.to_string(),
+        "variant `B`, field `0` (bytes 2..3): parse failed."
+    );
+}
+
+#[test]
+fn parse_error_no_match() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display("{a},{b}")]
+    struct X {
+        a: u32,
+        b: u32,
+    }
+    let e = "10".parse::<X>().unwrap_err();
+    assert_eq!(e, ParseError::new());
+    assert_eq!(e.to_string(), "parse failed.");
+
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    enum Y {
+        #[display("a={0}")]
+        A(u32),
+        B,
+    }
+    assert_eq!("c=1".parse::<Y>(), Err(ParseError::new()));
+}
+
+#[test]
+fn parse_error_source() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display("{a},{b}")]
+    struct X {
+        a: u32,
+        b: u32,
+    }
+    let e = "10,abc".parse::<X>().unwrap_err();
+    let source = e.source().unwrap();
+    assert!(source.downcast_ref::<ParseIntError>().is_some());
+    assert_eq!(source.to_string(), "invalid digit found in string");
+}
+
+#[test]
+fn parse_error_source_not_error() {
+    #[derive(Debug, Eq, PartialEq)]
+    struct Y;
+
+    impl FromStr for Y {
+        type Err = ();
+        fn from_str(_: &str) -> Result<Self, Self::Err> {
+            Err(())
+        }
+    }
+
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display("{0}")]
+    struct X(Y);
+
+    let e = "a".parse::<X>().unwrap_err();
+    assert!(e.source().is_none());
+    assert_eq!(e.to_string(), "field `0` (bytes 0..1): parse failed.");
+}
+
+#[test]
+fn parse_error_eq_ignores_source() {
+    let e = ParseError::new().with_source("x".parse::<u8>().unwrap_err());
+    assert_eq!(e, ParseError::new());
+}
+
+#[test]
+fn parse_error_expected() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display(style = "lowercase")]
+    enum Level {
+        Debug,
+        Info,
+        #[display("warning")]
+        Warn,
+    }
+    let e = "verbose".parse::<Level>().unwrap_err();
+    assert_eq!(e.expected(), ["debug", "info", "warning"]);
+    assert_eq!(e.input(), Some("verbose"));
+    assert_eq!(e.suggestion(), None);
+    assert_eq!(
+        e.to_string(),
+        "expected one of `debug`, `info`, `warning`, got `verbose`"
+    );
+
+    let e = "INFO".parse::<Level>().unwrap_err();
+    assert_eq!(e.suggestion(), Some("info"));
+
+    let e = "warnin".parse::<Level>().unwrap_err();
+    assert_eq!(
+        e.to_string(),
+        "expected one of `debug`, `info`, `warning`, got `warnin` (did you mean `warning`?)"
+    );
+}
+
+#[test]
+fn parse_error_expected_one() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    enum X {
+        A,
+    }
+    let e = "B".parse::<X>().unwrap_err();
+    assert_eq!(e.to_string(), "expected `A`, got `B`");
+}
+
+#[test]
+fn parse_error_expected_ignore() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[allow(dead_code)]
+    enum X {
+        A,
+        #[from_str(ignore)]
+        B,
+    }
+    assert_eq!("C".parse::<X>().unwrap_err().expected(), ["A"]);
+}
+
+#[test]
+fn parse_error_expected_not_literal() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    enum X {
+        A,
+        #[display("b={0}")]
+        B(u32),
+    }
+    assert_eq!("C".parse::<X>().unwrap_err().expected(), [""; 0]);
+
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[allow(dead_code)]
+    enum Y {
+        A,
+        #[from_str(new = None)]
+        B,
+    }
+    assert_eq!("B".parse::<Y>().unwrap_err().expected(), [""; 0]);
+}
+
+#[derive(Debug, Eq, PartialEq)]
+enum MyError {
+    Format,
+    Int(ParseIntError),
+    New(&'static str),
+}
+impl From<ParseError> for MyError {
+    fn from(_: ParseError) -> Self {
+        MyError::Format
+    }
+}
+impl From<ParseIntError> for MyError {
+    fn from(e: ParseIntError) -> Self {
+        MyError::Int(e)
+    }
+}
+impl From<Infallible> for MyError {
+    fn from(e: Infallible) -> Self {
+        match e {}
+    }
+}
+
+#[test]
+fn from_str_error_struct() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display("{a},{b}")]
+    #[from_str(error = MyError)]
+    struct X {
+        a: u32,
+        b: u32,
+    }
+    assert_eq!("1,2".parse(), Ok(X { a: 1, b: 2 }));
+    assert!(matches!("1,x".parse::<X>(), Err(MyError::Int(_))));
+    assert_eq!("1".parse::<X>(), Err(MyError::Format));
+}
+
+#[test]
+fn from_str_error_deep_field() {
+    #[derive(Debug, Default, Eq, PartialEq)]
+    struct X {
+        a: u32,
+    }
+
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display("_{x.a}")]
+    #[from_str(error = MyError)]
+    struct Y {
+        #[from_str(default)]
+        x: X,
+    }
+    assert_eq!("_1".parse(), Ok(Y { x: X { a: 1 } }));
+    assert!(matches!("_z".parse::<Y>(), Err(MyError::Int(_))));
+}
+
+#[test]
+fn from_str_error_enum() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[from_str(error = MyError)]
+    enum X {
+        #[display("a={0}")]
+        A(u32),
+        #[display("b={0}")]
+        B(u32),
+        C,
+    }
+    assert_eq!("b=1".parse(), Ok(X::B(1)));
+    assert_eq!("C".parse(), Ok(X::C));
+    assert!(matches!("b=x".parse::<X>(), Err(MyError::Int(_))));
+    assert_eq!("c=1".parse::<X>(), Err(MyError::Format));
+}
+
+#[test]
+fn from_str_error_enum_unit_only() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[from_str(error = MyError)]
+    enum X {
+        A,
+        B,
+    }
+    assert_eq!("A".parse(), Ok(X::A));
+    assert_eq!("C".parse::<X>(), Err(MyError::Format));
+}
+
+#[test]
+fn from_str_error_enum_later_variant() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[from_str(error = MyError)]
+    enum X {
+        #[display("{0}")]
+        A(u8),
+        #[display("{0}")]
+        B(String),
+    }
+    assert_eq!("1".parse(), Ok(X::A(1)));
+    assert_eq!("x".parse(), Ok(X::B("x".into())));
+}
+
+#[test]
+fn from_str_error_new_result() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display("{0}")]
+    #[from_str(error = MyError, new = Self::new(_0))]
+    struct X(u32);
+
+    impl X {
+        fn new(value: u32) -> Result<Self, MyError> {
+            if value == 0 {
+                Err(MyError::New("zero"))
+            } else {
+                Ok(Self(value))
+            }
+        }
+    }
+    assert_eq!("1".parse(), Ok(X(1)));
+    assert_eq!("0".parse::<X>(), Err(MyError::New("zero")));
+    assert!(matches!("x".parse::<X>(), Err(MyError::Int(_))));
+}
+
+#[test]
+fn from_str_error_new_option() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display("{0}")]
+    #[from_str(error = MyError, new = (_0 != 0).then_some(Self(_0)))]
+    struct X(u32);
+
+    assert_eq!("1".parse(), Ok(X(1)));
+    assert_eq!("0".parse::<X>(), Err(MyError::Format));
+}
+
+#[test]
+fn from_str_error_new_value() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display("{0}")]
+    #[from_str(error = MyError, new = Self(_0 + 1))]
+    struct X(u32);
+
+    assert_eq!("1".parse(), Ok(X(2)));
+}
+
+#[test]
+fn from_str_error_new_variant() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[from_str(error = MyError)]
+    enum X {
+        #[display("{0}")]
+        #[from_str(new = Self::new_a(_0))]
+        A(u32),
+        #[display("b{0}")]
+        B(u64),
+    }
+    impl X {
+        fn new_a(value: u32) -> Result<Self, MyError> {
+            if value < 10 {
+                Ok(Self::A(value))
+            } else {
+                Err(MyError::New("large"))
+            }
+        }
+    }
+    assert_eq!("1".parse(), Ok(X::A(1)));
+    assert_eq!("b10".parse(), Ok(X::B(10)));
+    assert_eq!("10".parse::<X>(), Err(MyError::New("large")));
+}
+
+#[test]
+fn from_str_error_generic() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display("{0}")]
+    #[from_str(error = MyError)]
+    struct X<T>(T);
+
+    assert_eq!("1".parse(), Ok(X(1u32)));
+    assert!(matches!("x".parse::<X<u32>>(), Err(MyError::Int(_))));
+}
+
+#[test]
+fn from_str_radix() {
+    #[derive(Display, FromStr, Debug, Eq, PartialEq)]
+    #[display("{0:x},{1:X},{2:o},{3:b}")]
+    struct X(u32, u32, u8, u16);
+    assert_from_str("ff,FF,17,101", X(255, 255, 15, 5));
+    assert_from_str("Ff,fF,17,101", X(255, 255, 15, 5));
+    assert_from_str_err::<X>("0xff,FF,17,101");
+    assert_from_str_err::<X>("ff,FF,18,101");
+    assert_from_str_err::<X>("ff,FF,17,102");
+}
+
+#[test]
+fn from_str_radix_alternate() {
+    #[derive(Display, FromStr, Debug, Eq, PartialEq)]
+    #[display("{0:#x},{1:#o},{2:#b}")]
+    struct X(u32, u32, u32);
+    assert_from_str("0xff,0o17,0b101", X(255, 15, 5));
+    assert_from_str("ff,17,101", X(255, 15, 5));
+    assert_from_str_err::<X>("0x0xff,17,101");
+    assert_from_str_err::<X>("0xff,0x17,101");
+}
+
+#[test]
+fn from_str_radix_signed() {
+    #[derive(Display, FromStr, Debug, Eq, PartialEq)]
+    #[display("{0:x}")]
+    struct X(i8);
+    assert_eq!(X(-1).to_string(), "ff");
+    assert_from_str("ff", X(-1));
+    assert_from_str("7f", X(127));
+    assert_from_str("80", X(-128));
+    assert_from_str_err::<X>("-1");
+    assert_from_str_err::<X>("100");
+}
+
+#[test]
+fn from_str_radix_field_format() {
+    #[derive(Display, FromStr, Debug, Eq, PartialEq)]
+    #[display("{a}{b}")]
+    struct X {
+        #[display("<{:x}>")]
+        a: u32,
+        #[display("[{:b}]")]
+        b: u8,
+    }
+    assert_from_str("<1f>[101]", X { a: 31, b: 5 });
+}
+
+#[test]
+fn from_str_radix_adjacent() {
+    #[derive(Display, FromStr, Debug, Eq, PartialEq)]
+    #[display("{0:x}{1}")]
+    struct X(u32, String);
+    assert_from_str("1fxyz", X(31, "xyz".into()));
+}
+
+#[test]
+fn from_str_radix_not_integer() {
+    #[derive(Debug, Eq, PartialEq)]
+    struct Hex(u32);
+    impl FromStr for Hex {
+        type Err = ParseIntError;
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            Ok(Hex(u32::from_str_radix(s, 16)?))
+        }
+    }
+    impl std::fmt::LowerHex for Hex {
+        fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            std::fmt::LowerHex::fmt(&self.0, f)
+        }
+    }
+
+    #[derive(Display, FromStr, Debug, Eq, PartialEq)]
+    #[display("{0:x}")]
+    struct X(Hex);
+    assert_from_str("1f", X(Hex(31)));
+}
+
+#[test]
+fn from_str_radix_with() {
+    #[derive(Display, FromStr, Debug, Eq, PartialEq)]
+    #[display("{0:x}")]
+    struct X(#[from_str(regex = "[0-9]+")] u32);
+    assert_from_str("10", X(10));
+    assert_from_str_err::<X>("1f");
+}
+
+#[test]
+fn from_str_padding() {
+    #[derive(Display, FromStr, Debug, Eq, PartialEq)]
+    #[display("{id:>8}|{name:<12}|")]
+    struct X {
+        id: u32,
+        name: String,
+    }
+    let value = X {
+        id: 42,
+        name: "abc".into(),
+    };
+    assert_eq!(value.to_string(), "      42|abc         |");
+    assert_from_str("      42|abc         |", value);
+    assert_from_str(
+        "123456789|abcdefghijklmn|",
+        X {
+            id: 123456789,
+            name: "abcdefghijklmn".into(),
+        },
+    );
+}
+
+#[test]
+fn from_str_padding_adjacent() {
+    #[derive(Display, FromStr, Debug, Eq, PartialEq)]
+    #[display("{0:>4}{1:>4}{2}")]
+    struct X(u32, u32, String);
+    assert_eq!(X(1, 23, "x".into()).to_string(), "   1  23x");
+    assert_from_str("   1  23x", X(1, 23, "x".into()));
+    assert_from_str("   1  23", X(1, 23, "".into()));
+    assert_from_str_err::<X>("  1  23x");
+}
+
+#[test]
+fn from_str_padding_fill() {
+    #[derive(Display, FromStr, Debug, Eq, PartialEq)]
+    #[display("{0:*^7}|{1:0>4}|{2:_<4}")]
+    struct X(String, u32, char);
+    assert_eq!(X("ab".into(), 0, 'c').to_string(), "**ab***|0000|c___");
+    assert_from_str("**ab***|0000|c___", X("ab".into(), 0, 'c'));
+    assert_from_str("**ab***|0012|c___", X("ab".into(), 12, 'c'));
+}
+
+#[test]
+fn from_str_padding_default_align() {
+    #[derive(Display, FromStr, Debug, PartialEq)]
+    #[display("[{0:6}][{1:6}][{2:6}]")]
+    struct X(i32, f64, bool);
+    assert_eq!(X(-5, 1.5, true).to_string(), "[    -5][   1.5][true  ]");
+    assert_from_str("[    -5][   1.5][true  ]", X(-5, 1.5, true));
+}
+
+#[test]
+fn from_str_padding_radix() {
+    #[derive(Display, FromStr, Debug, Eq, PartialEq)]
+    #[display("{0:>4x}{1:#06b}")]
+    struct X(u32, u8);
+    assert_eq!(X(255, 2).to_string(), "  ff0b0010");
+    assert_from_str("  ff0b0010", X(255, 2));
+}
+
+#[test]
+fn from_str_padding_not_padded_type() {
+    #[derive(Display, FromStr, Debug, Eq, PartialEq)]
+    #[display("{0}")]
+    struct Inner(u32);
+
+    #[derive(Display, FromStr, Debug, Eq, PartialEq)]
+    #[display("{0:>8}|")]
+    struct X(Inner);
+    assert_eq!(X(Inner(5)).to_string(), "5|");
+    assert_from_str("5|", X(Inner(5)));
+    assert_from_str("   5|", X(Inner(5)));
+}
+
+#[test]
+fn from_str_padding_generic() {
+    #[derive(Display, FromStr, Debug, Eq, PartialEq)]
+    #[display("{0:>4}")]
+    struct X<T>(T);
+    assert_from_str("  12", X(12u32));
+}
+
+#[test]
+fn from_str_sign_and_zero() {
+    #[derive(Display, FromStr, Debug, Eq, PartialEq)]
+    #[display("{0:08},{1:+},{2:+08},{3:+x}")]
+    struct X(i32, i32, i32, u8);
+    let value = X(-42, 42, -42, 42);
+    assert_eq!(value.to_string(), "-0000042,+42,-0000042,+2a");
+    assert_from_str("-0000042,+42,-0000042,+2a", value);
+    assert_from_str("-0000042,42,-0000042,2a", X(-42, 42, -42, 42));
+}
+
+#[test]
+fn from_str_strict() {
+    #[derive(Display, FromStr, Debug, PartialEq)]
+    #[display("{0:08},{1:+},{2},{3:.2}")]
+    #[from_str(strict)]
+    struct X(i32, u32, u32, f64);
+    let value = X(-42, 42, 7, 1.5);
+    assert_eq!(value.to_string(), "-0000042,+42,7,1.50");
+    assert_from_str("-0000042,+42,7,1.50", value);
+    assert_from_str_err::<X>("-42,+42,7,1.50");
+    assert_from_str_err::<X>("-0000042,42,7,1.50");
+    assert_from_str_err::<X>("-0000042,+42,+7,1.50");
+    assert_from_str_err::<X>("-0000042,+42,07,1.50");
+    assert_from_str_err::<X>("-0000042,+42,7,1.5");
+
+    let e = "-0000042,+42,07,1.50".parse::<X>().unwrap_err();
+    assert_eq!(e.field(), Some("2"));
+    assert_eq!(e.span(), Some(13..15));
+    assert_eq!(e.message(), "not formatted as `{:}`.");
+}
+
+#[test]
+fn from_str_strict_field() {
+    #[derive(Display, FromStr, Debug, Eq, PartialEq)]
+    #[display("{a:+},{b:+}")]
+    struct X {
+        #[from_str(strict)]
+        a: i32,
+        b: i32,
+    }
+    assert_from_str("+1,+2", X { a: 1, b: 2 });
+    assert_from_str("+1,2", X { a: 1, b: 2 });
+    assert_from_str_err::<X>("1,+2");
+}
+
+#[test]
+fn from_str_strict_variant() {
+    #[derive(Display, FromStr, Debug, Eq, PartialEq)]
+    enum X {
+        #[display("a{0:02}")]
+        #[from_str(strict)]
+        A(u8),
+        #[display("b{0:02}")]
+        B(u8),
+    }
+    assert_from_str("a01", X::A(1));
+    assert_from_str_err::<X>("a+1");
+    assert_from_str("b+1", X::B(1));
+}
+
+#[test]
+fn from_str_strict_opt() {
+    #[derive(Display, FromStr, Debug, Eq, PartialEq)]
+    #[display("[{0:03}]")]
+    #[from_str(strict)]
+    struct X(#[display(opt)] Option<u32>);
+    assert_from_str("[031]", X(Some(31)));
+    assert_from_str("[]", X(None));
+    assert_from_str_err::<X>("[31]");
+}
+
+#[test]
+fn from_str_strict_custom_error() {
+    #[derive(Display, FromStr, Debug, Eq, PartialEq)]
+    #[display("{0:03}")]
+    #[from_str(strict, error = MyError)]
+    struct X(u32);
+    assert!(matches!("001".parse::<X>(), Ok(X(1))));
+    assert!(matches!("1".parse::<X>(), Err(MyError::Format)));
+}
+
+#[test]
+fn from_str_ignore_case_enum() {
+    #[derive(Display, FromStr, Debug, Eq, PartialEq)]
+    #[display(style = "lowercase")]
+    #[from_str(ignore_case)]
+    enum Level {
+        Info,
+        Warn,
+    }
+    assert_eq!(Level::Info.to_string(), "info");
+    assert_from_str("info", Level::Info);
+    assert_from_str("INFO", Level::Info);
+    assert_from_str("wArN", Level::Warn);
+    assert_from_str_err::<Level>("infoo");
+
+    let e = "WARNING".parse::<Level>().unwrap_err();
+    assert_eq!(e.expected(), &["info", "warn"]);
+}
+
+#[test]
+fn from_str_ignore_case_variant() {
+    #[derive(Display, FromStr, Debug, Eq, PartialEq)]
+    enum X {
+        #[from_str(ignore_case)]
+        A,
+        B,
+        #[display("c{0}")]
+        #[from_str(ignore_case)]
+        C(u32),
+        #[display("d{0}")]
+        D(u32),
+    }
+    assert_from_str("a", X::A);
+    assert_from_str_err::<X>("b");
+    assert_from_str("B", X::B);
+    assert_from_str("C5", X::C(5));
+    assert_from_str_err::<X>("D5");
+}
+
+#[test]
+fn from_str_ignore_case_struct() {
+    #[derive(Display, FromStr, Debug, Eq, PartialEq)]
+    #[display("Point({x}, {y})")]
+    #[from_str(ignore_case)]
+    struct Point {
+        x: String,
+        y: String,
+    }
+    assert_from_str(
+        "point(Ab, cD)",
+        Point {
+            x: "Ab".into(),
+            y: "cD".into(),
+        },
+    );
+    assert_from_str(
+        "POINT(ÄÖ, Ü)",
+        Point {
+            x: "ÄÖ".into(),
+            y: "Ü".into(),
+        },
+    );
+}
+
+#[test]
+fn from_str_ignore_case_regex() {
+    #[derive(Display, FromStr, Debug, Eq, PartialEq)]
+    #[display("x{0}")]
+    #[from_str(ignore_case)]
+    struct X(#[from_str(regex = "[0-9]+")] u32);
+    assert_from_str("X12", X(12));
+
+    let re = regex::Regex::new(&format!("^(?:{})$", X::from_str_regex())).unwrap();
+    assert!(re.is_match("X12"));
+
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[from_str(ignore_case)]
+    enum Y {
+        Abc,
+        #[display("d{0}")]
+        D(u32),
+    }
+    let re = regex::Regex::new(&format!("^(?:{})$", Y::from_str_regex())).unwrap();
+    assert!(re.is_match("aBC"));
+    assert!(re.is_match("D1"));
+}
+
+#[test]
+fn from_str_alias() {
+    #[derive(Display, FromStr, Debug, Eq, PartialEq)]
+    #[display(style = "lowercase")]
+    enum Level {
+        #[from_str(alias = "information")]
+        Info,
+        #[display("warning")]
+        #[from_str(alias = "warn")]
+        #[from_str(alias = "w")]
+        Warning,
+        #[from_str(aliases = ["err", "e"])]
+        Error,
+    }
+    assert_eq!(Level::Warning.to_string(), "warning");
+    assert_from_str("info", Level::Info);
+    assert_from_str("information", Level::Info);
+    assert_from_str("warning", Level::Warning);
+    assert_from_str("warn", Level::Warning);
+    assert_from_str("w", Level::Warning);
+    assert_from_str("error", Level::Error);
+    assert_from_str("err", Level::Error);
+    assert_from_str("e", Level::Error);
+    assert_from_str_err::<Level>("i");
+
+    let re = regex::Regex::new(&format!("^(?:{})$", Level::from_str_regex())).unwrap();
+    assert!(re.is_match("information"));
+    assert!(re.is_match("warn"));
+    assert!(re.is_match("e"));
+    assert!(!re.is_match("i"));
+}
+
+#[test]
+fn from_str_alias_expected() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    enum Switch {
+        #[from_str(alias = "on")]
+        Enabled,
+        #[from_str(aliases = ["off", "no"])]
+        Disabled,
+    }
+    let e = "onn".parse::<Switch>().unwrap_err();
+    assert_eq!(e.expected(), ["Enabled", "on", "Disabled", "off", "no"]);
+    assert_eq!(e.suggestion(), Some("on"));
+    assert_eq!(
+        e.to_string(),
+        "expected one of `Enabled`, `on`, `Disabled`, `off`, `no`, got `onn` (did you mean `on`?)"
+    );
+}
+
+#[test]
+fn from_str_alias_ignore_case() {
+    #[derive(Display, FromStr, Debug, Eq, PartialEq)]
+    #[from_str(ignore_case)]
+    enum X {
+        #[from_str(alias = "a2")]
+        A,
+    }
+    assert_from_str("A2", X::A);
+}
+
+#[test]
+fn from_str_alias_struct() {
+    #[derive(Display, FromStr, Debug, Eq, PartialEq)]
+    #[display("none")]
+    #[from_str(aliases = ["null", "nil"])]
+    struct Nothing;
+    assert_from_str("none", Nothing);
+    assert_from_str("nil", Nothing);
+    assert_from_str_err::<Nothing>("nul");
+
+    let re = regex::Regex::new(&format!("^(?:{})$", Nothing::from_str_regex())).unwrap();
+    assert!(re.is_match("null"));
+}
+
+#[test]
+fn from_str_other() {
+    #[derive(Display, FromStr, Debug, Eq, PartialEq)]
+    #[display(style = "UPPERCASE")]
+    enum Method {
+        Get,
+        Post,
+        #[from_str(other)]
+        Other(String),
+    }
+    assert_eq!(Method::Get.to_string(), "GET");
+    assert_eq!(Method::Other("PATCH".into()).to_string(), "PATCH");
+    assert_from_str("GET", Method::Get);
+    assert_from_str("PATCH", Method::Other("PATCH".into()));
+    assert_from_str("", Method::Other("".into()));
+
+    let re = regex::Regex::new(&format!("^(?:{})$", Method::from_str_regex())).unwrap();
+    assert!(re.is_match("GET"));
+    assert!(re.is_match("PATCH"));
+}
+
+#[test]
+fn from_str_other_last() {
+    #[derive(Display, FromStr, Debug, Eq, PartialEq)]
+    enum X {
+        #[from_str(other)]
+        Other {
+            value: String,
+        },
+        #[display("a{0}")]
+        A(u32),
+        B,
+    }
+    assert_eq!(
+        X::Other {
+            value: "xyz".into()
+        }
+        .to_string(),
+        "xyz"
+    );
+    assert_from_str("a1", X::A(1));
+    assert_from_str("B", X::B);
+    assert_from_str("ax", X::Other { value: "ax".into() });
+}
+
+#[test]
+fn from_str_other_not_string() {
+    #[derive(Display, FromStr, Debug, Eq, PartialEq)]
+    enum X {
+        A,
+        #[from_str(other)]
+        Code(u32),
+    }
+    assert_from_str("A", X::A);
+    assert_from_str("10", X::Code(10));
+    assert_from_str_err::<X>("B");
+}
+
+#[test]
+fn from_str_custom_style() {
+    #[derive(Display, FromStr, Debug, Eq, PartialEq)]
+    #[display(style = "{lower}:{sep=/}")]
+    enum X {
+        AbcDef,
+        #[display(style = "Train-Case")]
+        XyzXyz,
+    }
+    assert_from_str("abc/def", X::AbcDef);
+    assert_from_str("Xyz-Xyz", X::XyzXyz);
+}
+
+#[test]
+fn from_str_struct_field_name() {
+    #[derive(Display, FromStr, Debug, Eq, PartialEq)]
+    #[display("{user_id}, {full_name}", style = "kebab-case")]
+    struct X {
+        #[display("{#name}={}")]
+        user_id: u32,
+        #[display("{#name}={}")]
+        full_name: String,
+    }
+    assert_from_str(
+        "user-id=1, full-name=abc",
+        X {
+            user_id: 1,
+            full_name: "abc".into(),
+        },
+    );
+    assert_from_str_err::<X>("user_id=1, full-name=abc");
+}
+
+#[test]
+fn from_str_enum_field_name() {
+    #[derive(Display, FromStr, Debug, Eq, PartialEq)]
+    #[display(style = "SNAKE_CASE")]
+    enum X {
+        #[display("{user_id}")]
+        A {
+            #[display("{#name}:{}")]
+            user_id: u32,
+        },
+    }
+    assert_from_str("USER_ID:5", X::A { user_id: 5 });
+}
+
+#[test]
+fn from_str_kv() {
+    #[derive(Display, FromStr, Debug, Eq, PartialEq)]
+    #[display(kv)]
+    struct X {
+        a: u32,
+        b: String,
+        c: bool,
+    }
+    let value = X {
+        a: 1,
+        b: "foo".into(),
+        c: true,
+    };
+    assert_from_str("a=1 b=foo c=true", value);
+    assert_from_str(
+        "c=false a=2 b=bar",
+        X {
+            a: 2,
+            b: "bar".into(),
+            c: false,
+        },
+    );
+    assert_from_str_err::<X>("a=1 b=foo");
+    assert_from_str_err::<X>("a=1 b=foo c=true d=1");
+    assert_from_str_err::<X>("a=1 b=foo c=true a=2");
+    assert_from_str_err::<X>("a=1 b=foo c");
+    assert_from_str_err::<X>("");
+}
+
+#[test]
+fn from_str_kv_errors() {
+    #[derive(Display, FromStr, Debug, Eq, PartialEq)]
+    #[display(kv)]
+    struct X {
+        a: u32,
+        b: u32,
+    }
+    let e = "a=1 b=x".parse::<X>().unwrap_err();
+    assert_eq!(e.field(), Some("b"));
+    assert_eq!(e.span(), Some(6..7));
+
+    let e = "a=1".parse::<X>().unwrap_err();
+    assert_eq!(e.message(), "missing key.");
+    assert_eq!(e.field(), Some("b"));
+
+    let e = "a=1 c=2 b=3".parse::<X>().unwrap_err();
+    assert_eq!(e.message(), "unknown key.");
+    assert_eq!(e.span(), Some(4..5));
+
+    let e = "a=1 b=2 a=3".parse::<X>().unwrap_err();
+    assert_eq!(e.message(), "duplicate key.");
+    assert_eq!(e.field(), Some("a"));
+    assert_eq!(e.span(), Some(8..9));
+
+    let e = "a=1 b".parse::<X>().unwrap_err();
+    assert_eq!(e.message(), "expected a key-value pair.");
+    assert_eq!(e.span(), Some(4..5));
+}
+
+#[test]
+fn from_str_kv_sep_assign_style() {
+    #[derive(Display, FromStr, Debug, Eq, PartialEq)]
+    #[display(kv(sep = ", ", assign = ": "), style = "kebab-case")]
+    struct X {
+        user_id: u32,
+        full_name: String,
+    }
+    assert_from_str(
+        "full-name: abc, user-id: 1",
+        X {
+            user_id: 1,
+            full_name: "abc".into(),
+        },
+    );
+    assert_from_str_err::<X>("user_id: 1, full_name: abc");
+}
+
+#[test]
+fn from_str_kv_ignore_unknown_keys() {
+    #[derive(Display, FromStr, Debug, Eq, PartialEq)]
+    #[display(kv)]
+    #[from_str(ignore_unknown_keys)]
+    struct X {
+        a: u32,
+        b: u32,
+    }
+    assert_from_str("x=0 a=1 y=z b=2", X { a: 1, b: 2 });
+    assert_from_str_err::<X>("a=1 b=2 c");
+}
+
+#[test]
+fn from_str_kv_ignore_case() {
+    #[derive(Display, FromStr, Debug, Eq, PartialEq)]
+    #[display(kv)]
+    #[from_str(ignore_case)]
+    struct X {
+        abc: u32,
+    }
+    assert_from_str("ABC=1", X { abc: 1 });
+}
+
+#[test]
+fn from_str_kv_default_field() {
+    #[derive(Display, FromStr, Debug, Eq, PartialEq)]
+    #[display(kv)]
+    struct X {
+        a: u32,
+        #[from_str(default)]
+        b: u32,
+    }
+    assert_from_str("a=1", X { a: 1, b: 0 });
+    assert_from_str("b=2 a=1", X { a: 1, b: 2 });
+    assert_from_str_err::<X>("b=2");
+}
+
+#[test]
+fn from_str_kv_default_struct() {
+    #[derive(Display, FromStr, Debug, Eq, PartialEq)]
+    #[display(kv)]
+    #[from_str(default)]
+    struct X {
+        a: u32,
+        b: u32,
+    }
+    impl Default for X {
+        fn default() -> Self {
+            Self { a: 10, b: 20 }
+        }
+    }
+    assert_from_str("a=1", X { a: 1, b: 20 });
+    assert_from_str("", X { a: 10, b: 20 });
+}
+
+#[test]
+fn from_str_kv_opt() {
+    #[derive(Display, FromStr, Debug, Eq, PartialEq)]
+    #[display(kv)]
+    struct X {
+        #[display(opt)]
+        a: Option<u32>,
+        b: u32,
+    }
+    assert_from_str("a=1 b=2", X { a: Some(1), b: 2 });
+    assert_from_str("b=2", X { a: None, b: 2 });
+}
+
+#[test]
+fn from_str_kv_with() {
+    struct Plus1;
+    impl FromStrFormat<i32> for Plus1 {
+        type Err = std::num::ParseIntError;
+        fn parse(&self, s: &str) -> core::result::Result<i32, Self::Err> {
+            s.parse::<i32>().map(|x| x + 1)
+        }
+    }
+
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display(kv)]
+    struct X {
+        #[from_str(with = Plus1)]
+        a: i32,
+    }
+    assert_from_str("a=12", X { a: 13 });
+}
+
+#[test]
+fn from_str_kv_custom_error() {
+    #[derive(Display, FromStr, Debug, Eq, PartialEq)]
+    #[display(kv)]
+    #[from_str(error = MyError)]
+    struct X {
+        a: u32,
+    }
+    #[derive(Debug, Eq, PartialEq)]
+    struct MyError;
+    impl From<ParseError> for MyError {
+        fn from(_: ParseError) -> Self {
+            MyError
+        }
+    }
+    impl From<ParseIntError> for MyError {
+        fn from(_: ParseIntError) -> Self {
+            MyError
+        }
+    }
+    assert_eq!("a=1".parse::<X>(), Ok(X { a: 1 }));
+    assert_eq!("b=1".parse::<X>(), Err(MyError));
+    assert_eq!("a=x".parse::<X>(), Err(MyError));
+}
+
+#[test]
+fn from_str_any_order() {
+    #[derive(Display, FromStr, Debug, Eq, PartialEq)]
+    #[display("x={x} y={y}")]
+    #[from_str(any_order)]
+    struct X {
+        x: u32,
+        y: u32,
+    }
+    assert_from_str("x=1 y=2", X { x: 1, y: 2 });
+    assert_from_str("y=2 x=1", X { x: 1, y: 2 });
+    assert_from_str_err::<X>("x=1");
+    assert_from_str_err::<X>("x=1 x=2");
+    assert_from_str_err::<X>("x=1 y=2 z=3");
+    assert_from_str_err::<X>("x=1 y=a");
+}
+
+#[test]
+fn from_str_any_order_infer_delimiter() {
+    #[derive(Display, FromStr, Debug, Eq, PartialEq)]
+    #[display("a: {a}, b: {b}, c: {c}")]
+    #[from_str(any_order)]
+    struct X {
+        a: u32,
+        b: String,
+        c: bool,
+    }
+    assert_from_str(
+        "c: true, a: 1, b: abc",
+        X {
+            a: 1,
+            b: "abc".into(),
+            c: true,
+        },
+    );
+}
+
+#[test]
+fn from_str_any_order_delimiter() {
+    #[derive(Display, FromStr, Debug, Eq, PartialEq)]
+    #[display("[{a}]/[{b}]/end")]
+    #[from_str(any_order = "/")]
+    struct X {
+        #[display("a{}")]
+        a: u32,
+        #[display("b{}")]
+        b: u32,
+    }
+    assert_from_str("[a1]/[b2]/end", X { a: 1, b: 2 });
+    assert_from_str("end/[b2]/[a1]", X { a: 1, b: 2 });
+    assert_from_str_err::<X>("[a1]/[b2]");
+}
+
+#[test]
+fn from_str_any_order_format_spec() {
+    #[derive(Display, FromStr, Debug, Eq, PartialEq)]
+    #[display("x={x:x} y={y:_>3}")]
+    #[from_str(any_order)]
+    struct X {
+        x: u32,
+        y: u32,
+    }
+    assert_from_str("y=__2 x=ff", X { x: 255, y: 2 });
+    assert_from_str("x=ff y=__2", X { x: 255, y: 2 });
+}
+
+#[test]
+fn from_str_any_order_ignore_case() {
+    #[derive(Display, FromStr, Debug, Eq, PartialEq)]
+    #[display("x={x} y={y}")]
+    #[from_str(any_order, ignore_case)]
+    struct X {
+        x: u32,
+        y: u32,
+    }
+    assert_from_str("Y=2 X=1", X { x: 1, y: 2 });
+}
+
+#[test]
+fn from_str_any_order_error_span() {
+    #[derive(Display, FromStr, Debug, Eq, PartialEq)]
+    #[display("x={x} y={y}")]
+    #[from_str(any_order)]
+    struct X {
+        x: u32,
+        y: u32,
+    }
+    let e = "y=a x=1".parse::<X>().unwrap_err();
+    assert_eq!(e.field(), Some("y"));
+    assert_eq!(e.span(), Some(2..3));
+}
+
+#[test]
+fn from_str_any_order_retry() {
+    #[derive(Display, FromStr, Debug, Eq, PartialEq)]
+    #[display("{a} {b}")]
+    #[from_str(any_order)]
+    struct X {
+        a: u32,
+        b: String,
+    }
+    assert_from_str(
+        "1 foo",
+        X {
+            a: 1,
+            b: "foo".into(),
+        },
+    );
+    assert_eq!(
+        "foo 1".parse(),
+        Ok(X {
+            a: 1,
+            b: "foo".into()
+        })
+    );
+    let e = "foo bar".parse::<X>().unwrap_err();
+    assert_eq!(e.field(), Some("a"));
+    assert_eq!(e.span(), Some(0..3));
+}
+
+#[test]
+fn from_str_any_order_limit() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display("{0} {1} {2} {3} {4} {5} {6} {7} {8} {9}")]
+    #[from_str(any_order)]
+    struct X(u8, u8, u8, u8, u8, u8, u8, u8, u8, u8);
+    let start = std::time::Instant::now();
+    let e = "1 2 3 4 5 6 7 8 9 x".parse::<X>().unwrap_err();
+    assert!(start.elapsed() < std::time::Duration::from_secs(1));
+    assert_eq!(e.span(), Some(18..19));
+}
+
+#[test]
+fn from_str_backtrack() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display("{a}{b}")]
+    #[from_str(backtrack)]
+    struct X {
+        a: u32,
+        b: String,
+    }
+    assert_from_str(
+        "1a",
+        X {
+            a: 1,
+            b: "a".into(),
+        },
+    );
+    assert_from_str(
+        "12",
+        X {
+            a: 12,
+            b: "".into(),
+        },
+    );
+    assert_from_str_err::<X>("a1");
+}
+
+#[test]
+fn from_str_backtrack_regex_infer() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display("{a}-{b}")]
+    #[from_str(regex_infer, backtrack)]
+    struct X {
+        a: String,
+        b: u8,
+    }
+    assert_from_str(
+        "x-1-2",
+        X {
+            a: "x-1".into(),
+            b: 2,
+        },
+    );
+}
+
+#[test]
+fn from_str_backtrack_limit() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display("{a}{b}")]
+    #[from_str(backtrack = 2)]
+    struct X {
+        a: u32,
+        b: String,
+    }
+    assert_from_str("1", X { a: 1, b: "".into() });
+    assert_from_str_err::<X>("1a");
+}
+
+#[test]
+fn from_str_backtrack_error() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display("{a},{b}")]
+    #[from_str(backtrack)]
+    struct X {
+        a: u32,
+        b: u32,
+    }
+    assert_from_str("1,2", X { a: 1, b: 2 });
+    let e = "1,x".parse::<X>().unwrap_err();
+    assert_eq!(e.field(), Some("b"));
+    assert_eq!(e.span(), Some(2..3));
+}
+
+#[test]
+fn from_str_backtrack_new() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display("{a}{b}")]
+    #[from_str(backtrack, new = Self::new(a, b))]
+    struct X {
+        a: String,
+        b: String,
+    }
+    impl X {
+        fn new(a: String, b: String) -> Option<Self> {
+            (a.len() == 2).then_some(Self { a, b })
+        }
+    }
+    assert_eq!(
+        "abc".parse(),
+        Ok(X {
+            a: "ab".into(),
+            b: "c".into()
+        })
+    );
+    assert_from_str_err::<X>("a");
+}
+
+#[test]
+fn from_str_backtrack_enum() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[from_str(backtrack)]
+    enum X {
+        #[display("{0}{1}")]
+        A(u32, String),
+        #[display("{0}+{1}")]
+        B(String, u32),
+    }
+    assert_from_str("1a", X::A(1, "a".into()));
+    assert_from_str("a+b+1", X::B("a+b".into(), 1));
+}
+
+#[test]
+fn from_str_backtrack_variant_custom_error() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[from_str(error = MyError)]
+    enum X {
+        #[display("{0}{1}")]
+        #[from_str(backtrack)]
+        A(u32, u8),
+    }
+    #[derive(Debug, Eq, PartialEq)]
+    struct MyError;
+    impl From<ParseError> for MyError {
+        fn from(_: ParseError) -> Self {
+            MyError
+        }
+    }
+    impl From<ParseIntError> for MyError {
+        fn from(_: ParseIntError) -> Self {
+            MyError
+        }
+    }
+    assert_eq!("12".parse::<X>(), Ok(X::A(1, 2)));
+    assert_eq!("1a".parse::<X>(), Err(MyError));
+}
+
+#[test]
+fn from_str_backtrack_opt() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display("{a}{b}")]
+    #[from_str(backtrack)]
+    struct X {
+        #[display("[{}]", opt)]
+        a: Option<u32>,
+        b: u32,
+    }
+    assert_from_str("[1]2", X { a: Some(1), b: 2 });
+    assert_from_str("2", X { a: None, b: 2 });
+    assert_from_str_err::<X>("[x]2");
+}
+
+#[test]
+fn from_str_prefix() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display("{x}:{y}")]
+    #[from_str(prefix)]
+    struct X {
+        x: u32,
+        y: u32,
+    }
+    assert_eq!(X::parse_prefix("1:2,3:4"), Ok((X { x: 1, y: 2 }, ",3:4")));
+    assert_eq!(X::parse_prefix("1:2"), Ok((X { x: 1, y: 2 }, "")));
+    assert!(X::parse_prefix("1,2").is_err());
+    assert!(X::parse_prefix(" 1:2").is_err());
+    assert_from_str("1:2", X { x: 1, y: 2 });
+}
+
+#[test]
+fn from_str_prefix_field_error() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display("{x}:{y}")]
+    #[from_str(prefix)]
+    struct X {
+        x: u8,
+        #[from_str(regex = "[0-9]+")]
+        y: u8,
+    }
+    let e = X::parse_prefix("1:300,").unwrap_err();
+    assert_eq!(e.field(), Some("y"));
+    assert_eq!(e.span(), Some(2..5));
+}
+
+#[test]
+fn from_str_prefix_custom_error() {
+    #[derive(Debug, Eq, PartialEq)]
+    struct MyError;
+    impl From<ParseError> for MyError {
+        fn from(_: ParseError) -> Self {
+            MyError
+        }
+    }
+    impl From<std::num::ParseIntError> for MyError {
+        fn from(_: std::num::ParseIntError) -> Self {
+            MyError
+        }
+    }
+
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display("<{0}>")]
+    #[from_str(prefix, error = MyError)]
+    struct X(u32);
+    assert_eq!(X::parse_prefix("<1>2"), Ok((X(1), "2")));
+    assert_eq!(X::parse_prefix("<x>"), Err(MyError));
+}
+
+#[test]
+fn from_str_prefix_enum() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[from_str(prefix)]
+    enum X {
+        #[display("+")]
+        Plus,
+        #[display("-")]
+        Minus,
+    }
+    assert_eq!(X::parse_prefix("+1"), Ok((X::Plus, "1")));
+    assert_eq!(X::parse_prefix("--"), Ok((X::Minus, "-")));
+    assert!(X::parse_prefix("*").is_err());
+}
+
+#[test]
+fn from_str_prefix_generic() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display("<{0}>")]
+    #[from_str(prefix)]
+    struct X<T>(T);
+    assert_eq!(X::<u8>::parse_prefix("<1><2>"), Ok((X(1), "<2>")));
+    assert_eq!(
+        X::<String>::parse_prefix("<a><b>"),
+        Ok((X("a".to_string()), "<b>"))
+    );
+}
+
+#[test]
+fn from_str_find() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display("{user}@{host}")]
+    #[from_str(find)]
+    struct X {
+        #[from_str(regex = "[a-z]+")]
+        user: String,
+        #[from_str(regex = "[a-z.]+")]
+        host: String,
+    }
+    let text = "from alice@example.com to bob@localhost";
+    let values: Vec<_> = X::find_iter(text).collect();
+    assert_eq!(
+        values,
+        vec![
+            (
+                5..22,
+                Ok(X {
+                    user: "alice".into(),
+                    host: "example.com".into(),
+                })
+            ),
+            (
+                26..39,
+                Ok(X {
+                    user: "bob".into(),
+                    host: "localhost".into(),
+                })
+            ),
+        ]
+    );
+    assert_eq!(X::find(text), values.into_iter().next());
+    assert_eq!(X::find("no address"), None);
+}
+
+#[test]
+fn from_str_find_agrees_with_from_str() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display("[{0}]")]
+    #[from_str(find)]
+    struct X(String);
+    let text = "a [b c] d [] e";
+    let values: Vec<_> = X::find_iter(text).collect();
+    assert_eq!(
+        values,
+        vec![(2..7, Ok(X("b c".into()))), (10..12, Ok(X(String::new())))]
+    );
+    for (range, value) in values {
+        assert_eq!(text[range].parse(), value);
+    }
+}
+
+#[test]
+fn from_str_find_regex_infer() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display("{x},{y}")]
+    #[from_str(find, regex_infer)]
+    struct X {
+        x: u8,
+        y: bool,
+    }
+    let values: Vec<_> = X::find_iter("(1,true) (2,no) (300,false)")
+        .map(|(r, v)| (r, v.unwrap()))
+        .collect();
+    assert_eq!(
+        values,
+        vec![(1..7, X { x: 1, y: true }), (18..26, X { x: 0, y: false })]
+    );
+}
+
+#[test]
+fn from_str_find_captures() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display("{key}={value}")]
+    #[from_str(find)]
+    struct X {
+        #[from_str(regex = "[a-z]+")]
+        key: String,
+        #[from_str(regex = "[0-9]+")]
+        value: u32,
+        #[from_str(default)]
+        note: String,
+    }
+    let c = X::captures("set a=1 b=2").unwrap();
+    assert_eq!(c.range(), 4..7);
+    assert_eq!(c.as_str(), "a=1");
+    assert_eq!(c.field("key"), Some("a"));
+    assert_eq!(c.field_range("value"), Some(6..7));
+    assert_eq!(c.field("note"), None);
+    assert_eq!(
+        c.into_value(),
+        Ok(X {
+            key: "a".into(),
+            value: 1,
+            note: String::new(),
+        })
+    );
+    assert!(X::captures("none").is_none());
+    assert_eq!(X::captures_at("a=1 b=2", 1).map(|c| c.range()), Some(4..7));
+}
+
+#[test]
+fn from_str_find_parse_error() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display("#{0}")]
+    #[from_str(find)]
+    struct X(#[from_str(regex = "[0-9]+")] u8);
+    let values: Vec<_> = X::find_iter("#1 #300 #2")
+        .map(|(r, v)| (r, v.ok()))
+        .collect();
+    assert_eq!(
+        values,
+        vec![(0..2, Some(X(1))), (3..7, None), (8..10, Some(X(2)))]
+    );
+}
+
+#[test]
+fn from_str_find_enum() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[from_str(find)]
+    enum X {
+        #[display("yes")]
+        Yes,
+        #[display("no")]
+        No,
+    }
+    let values: Vec<_> = X::find_iter("yes, no, maybe")
+        .map(|(_, v)| v.unwrap())
+        .collect();
+    assert_eq!(values, vec![X::Yes, X::No]);
+}
+
+#[test]
+fn from_str_find_generic() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display("<{0}>")]
+    #[from_str(find, regex_infer)]
+    struct X<T: FromStrRegex>(T);
+    let values: Vec<_> = X::<u8>::find_iter("<1> <a> <2>")
+        .map(|(_, v)| v.unwrap())
+        .collect();
+    assert_eq!(values, vec![X(1), X(2)]);
+
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display("<{0}>")]
+    #[from_str(find)]
+    struct Y<T>(T);
+    let values: Vec<_> = Y::<u8>::find_iter("<1> <2>")
+        .map(|(_, v)| v.unwrap())
+        .collect();
+    assert_eq!(values, vec![Y(1), Y(2)]);
+}
+
+#[test]
+fn from_str_opt_none_value() {
+    #[derive(Display, FromStr, Debug, Eq, PartialEq)]
+    #[display("{a},{b}")]
+    struct X {
+        #[display(opt = "-")]
+        a: Option<u8>,
+        #[display("b={}", opt = "null")]
+        b: Option<String>,
+    }
+    assert_from_str(
+        "10,b=x",
+        X {
+            a: Some(10),
+            b: Some("x".into()),
+        },
+    );
+    assert_from_str("-,null", X { a: None, b: None });
+    assert_from_str(
+        "-,b=null",
+        X {
+            a: None,
+            b: Some("null".into()),
+        },
+    );
+    assert_from_str_err::<X>(",null");
+}
+
+#[test]
+fn from_str_opt_none_value_string() {
+    #[derive(Display, FromStr, Debug, Eq, PartialEq)]
+    #[display("[{a}]")]
+    struct X {
+        #[display(opt = "-")]
+        a: Option<String>,
+    }
+    assert_from_str("[-]", X { a: None });
+    assert_from_str(
+        "[a]",
+        X {
+            a: Some("a".into()),
+        },
+    );
+    assert_eq!("[]".parse(), Ok(X { a: Some("".into()) }));
+}
+
+#[test]
+fn from_str_kv_opt_none_value() {
+    #[derive(Display, FromStr, Debug, Eq, PartialEq)]
+    #[display(kv)]
+    struct X {
+        #[display(opt = "-")]
+        a: Option<u32>,
+        b: u32,
+    }
+    assert_from_str("a=1 b=2", X { a: Some(1), b: 2 });
+    assert_from_str("a=- b=2", X { a: None, b: 2 });
+    assert_eq!("b=2".parse(), Ok(X { a: None, b: 2 }));
+}
+
+#[test]
+fn from_str_opt_empty() {
+    #[derive(Display, FromStr, Debug, Eq, PartialEq)]
+    #[display("{a}{b}")]
+    struct X {
+        a: u32,
+        #[display(" b={}", opt_empty)]
+        b: String,
+    }
+    assert_from_str(
+        "1 b=x",
+        X {
+            a: 1,
+            b: "x".into(),
+        },
+    );
+    assert_from_str(
+        "1",
+        X {
+            a: 1,
+            b: String::new(),
+        },
+    );
+}
+
+#[test]
+fn from_str_omit_if() {
+    fn is_zero(value: &u32) -> bool {
+        *value == 0
+    }
+
+    #[derive(Display, FromStr, Debug, Eq, PartialEq)]
+    #[display("{a}{b}")]
+    struct X {
+        a: u32,
+        #[display(" b={}", omit_if = is_zero)]
+        b: u32,
+    }
+    assert_from_str("1 b=2", X { a: 1, b: 2 });
+    assert_from_str("1", X { a: 1, b: 0 });
+    assert_from_str_err::<X>("1 b=");
+}
+
+#[test]
+fn from_str_kv_omit() {
+    #[derive(Display, FromStr, Debug, Eq, PartialEq)]
+    #[display(kv)]
+    struct X {
+        #[display(opt_empty)]
+        a: String,
+        b: u32,
+    }
+    assert_from_str(
+        "a=x b=2",
+        X {
+            a: "x".into(),
+            b: 2,
+        },
+    );
+    assert_from_str(
+        "b=2",
+        X {
+            a: String::new(),
+            b: 2,
+        },
+    );
+}
+
 #[track_caller]
 fn assert_from_str<T: FromStr + Debug + PartialEq>(s: &str, value: T)
 where
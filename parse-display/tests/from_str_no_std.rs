@@ -0,0 +1,147 @@
+use core::fmt::Debug;
+use core::str::FromStr;
+use parse_display::*;
+
+#[test]
+fn from_str_no_std_struct() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display("{a}-{b}")]
+    struct X {
+        a: u32,
+        b: u32,
+    }
+    assert_from_str("10-20", X { a: 10, b: 20 });
+    assert_from_str_err::<X>("10");
+    assert_from_str_err::<X>("10-x");
+}
+
+#[test]
+fn from_str_no_std_prefix_suffix() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display("[{0}, {1}]")]
+    struct X(u32, u32);
+    assert_from_str("[1, 2]", X(1, 2));
+    assert_from_str_err::<X>("[1, 2");
+    assert_from_str_err::<X>("1, 2]");
+    assert_from_str_err::<X>("[1 2]");
+}
+
+#[test]
+fn from_str_no_std_lazy() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display("{a}-{b}")]
+    struct X {
+        a: String,
+        b: String,
+    }
+    assert_from_str(
+        "1-2-3",
+        X {
+            a: "1".to_string(),
+            b: "2-3".to_string(),
+        },
+    );
+
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display("{a}{b}")]
+    struct Y {
+        a: String,
+        b: String,
+    }
+    assert_from_str(
+        "12",
+        Y {
+            a: "".to_string(),
+            b: "12".to_string(),
+        },
+    );
+}
+
+#[test]
+fn from_str_no_std_overlap() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display("ab{0}ba")]
+    struct X(String);
+    assert_from_str("abba", X("".to_string()));
+    assert_from_str_err::<X>("aba");
+}
+
+#[test]
+fn from_str_no_std_enum() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    enum X {
+        #[display("a={0}")]
+        A(u32),
+        #[display("b={0},{1}")]
+        B(u32, u32),
+        C,
+    }
+    assert_from_str("a=1", X::A(1));
+    assert_from_str("b=1,2", X::B(1, 2));
+    assert_from_str("C", X::C);
+    assert_from_str_err::<X>("b=1");
+}
+
+#[test]
+fn from_str_no_std_nested_field() {
+    #[derive(Debug, Default, Eq, PartialEq)]
+    struct X {
+        a: u32,
+    }
+
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display("_{x.a}_")]
+    struct Y {
+        #[from_str(default)]
+        x: X,
+    }
+    assert_from_str("_5_", Y { x: X { a: 5 } });
+}
+
+#[test]
+fn from_str_no_std_error() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display("{a}:{b}")]
+    struct X {
+        a: String,
+        b: u16,
+    }
+    let e = "localhost:http".parse::<X>().unwrap_err();
+    assert_eq!(e.field(), Some("b"));
+    assert_eq!(e.span(), Some(10..14));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn from_str_no_std_error_source() {
+    #[derive(FromStr, Debug, Eq, PartialEq)]
+    #[display("{a}-{b}")]
+    struct X {
+        a: u32,
+        b: u32,
+    }
+    let e = "1-x".parse::<X>().unwrap_err();
+    let source = core::error::Error::source(&e).unwrap();
+    assert!(source.is::<core::num::ParseIntError>());
+    assert_eq!(e.to_string(), "field `b` (bytes 2..3): parse failed.");
+}
+
+#[track_caller]
+fn assert_from_str<T: FromStr + Debug + PartialEq>(s: &str, value: T)
+where
+    <T as FromStr>::Err: Debug,
+{
+    match s.parse::<T>() {
+        Ok(a) => assert_eq!(a, value, "input = {s:?}"),
+        Err(e) => panic!("\"{s}\" parse failed. ({e:?})"),
+    }
+}
+
+#[track_caller]
+fn assert_from_str_err<T: FromStr + Debug>(s: &str)
+where
+    <T as FromStr>::Err: Debug,
+{
+    let a = s.parse::<T>();
+    assert!(a.is_err(), "input = {s:?}, result = {a:?}");
+}
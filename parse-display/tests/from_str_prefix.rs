@@ -0,0 +1,73 @@
+use core::num::NonZero;
+use std::fmt::Debug;
+
+use parse_display::FromStrPrefix;
+
+#[track_caller]
+fn assert_prefix<T: FromStrPrefix + Debug + PartialEq>(s: &str, value: T, rest: &str) {
+    match T::parse_prefix(s) {
+        Ok((v, r)) => {
+            assert_eq!(v, value, "input = {s:?}");
+            assert_eq!(r, rest, "input = {s:?}");
+        }
+        Err(_) => panic!("parse_prefix({s:?}) should return Ok."),
+    }
+}
+
+#[track_caller]
+fn assert_prefix_err<T: FromStrPrefix + Debug>(s: &str) {
+    if let Ok(a) = T::parse_prefix(s) {
+        panic!("parse_prefix({s:?}) should return Err. but return `{a:?}`.");
+    }
+}
+
+#[test]
+fn test_uint() {
+    assert_prefix("12abc", 12u32, "abc");
+    assert_prefix("+12 ", 12u32, " ");
+    assert_prefix("0", 0u8, "");
+    assert_prefix("3-4", NonZero::new(3u8).unwrap(), "-4");
+    assert_prefix_err::<u32>("-1");
+    assert_prefix_err::<u32>("abc");
+    assert_prefix_err::<u32>("");
+    assert_prefix_err::<u8>("256");
+    assert_prefix_err::<NonZero<u8>>("0,");
+}
+
+#[test]
+fn test_sint() {
+    assert_prefix("-12abc", -12i32, "abc");
+    assert_prefix("+12", 12i64, "");
+    assert_prefix("1-2", 1i8, "-2");
+    assert_prefix_err::<i32>("-");
+    assert_prefix_err::<i32>("x1");
+}
+
+#[test]
+fn test_f() {
+    assert_prefix("1.5x", 1.5f64, "x");
+    assert_prefix("-.5,", -0.5f64, ",");
+    assert_prefix("1.,", 1.0f64, ",");
+    assert_prefix("1e3x", 1000.0f64, "x");
+    assert_prefix("1E-3", 0.001f64, "");
+    assert_prefix("1ex", 1.0f32, "ex");
+    assert_prefix("inf,", f64::INFINITY, ",");
+    assert_prefix("-Infinity", f64::NEG_INFINITY, "");
+    assert!(f64::parse_prefix("NaN!").unwrap().0.is_nan());
+    assert_prefix_err::<f64>(".");
+    assert_prefix_err::<f64>("x");
+}
+
+#[test]
+fn test_bool() {
+    assert_prefix("true,", true, ",");
+    assert_prefix("falsey", false, "y");
+    assert_prefix_err::<bool>("True");
+}
+
+#[test]
+fn test_char() {
+    assert_prefix("abc", 'a', "bc");
+    assert_prefix("あい", 'あ', "い");
+    assert_prefix_err::<char>("");
+}
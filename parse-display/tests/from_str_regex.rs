@@ -1,7 +1,10 @@
 #![cfg(feature = "std")]
 
-use core::f32;
-use std::fmt::Display;
+use core::{f32, num::NonZero};
+use std::{
+    fmt::Display,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6},
+};
 
 use parse_display::FromStrRegex;
 use regex::Regex;
@@ -14,7 +17,11 @@ fn assert_match_str<T: FromStrRegex>(s: &str, should_match: bool) {
         std::any::type_name::<T>(),
         re
     );
-    assert_eq!(Regex::new(&re).unwrap().is_match(s), should_match, "{msg}");
+    assert_eq!(
+        Regex::new(&format!("^(?:{re})$")).unwrap().is_match(s),
+        should_match,
+        "{msg}"
+    );
 
     let is_match = s.parse::<T>().is_ok();
     assert_eq!(is_match, should_match, "{msg}");
@@ -152,3 +159,110 @@ fn test_string() {
     assert_match(String::from(" "));
     assert_match(String::from("\n\t\r"));
 }
+
+#[test]
+fn test_ipv4_addr() {
+    assert_match(Ipv4Addr::UNSPECIFIED);
+    assert_match(Ipv4Addr::LOCALHOST);
+    assert_match(Ipv4Addr::BROADCAST);
+    assert_match(Ipv4Addr::new(192, 168, 10, 200));
+    assert_match_str::<Ipv4Addr>("1.2.3.256", false);
+    assert_match_str::<Ipv4Addr>("01.2.3.4", false);
+    assert_match_str::<Ipv4Addr>("1.2.3", false);
+    assert_match_str::<Ipv4Addr>("1.2.3.4.5", false);
+    assert_match_str::<Ipv4Addr>("", false);
+}
+
+#[test]
+fn test_ipv6_addr() {
+    assert_match(Ipv6Addr::UNSPECIFIED);
+    assert_match(Ipv6Addr::LOCALHOST);
+    assert_match(Ipv6Addr::new(1, 2, 3, 4, 5, 6, 7, 8));
+    assert_match(Ipv6Addr::new(1, 0, 0, 0, 0, 0, 0, 8));
+    assert_match(Ipv6Addr::new(0xfe80, 0, 0, 0, 0, 0, 0, 0));
+    assert_match(Ipv4Addr::new(1, 2, 3, 4).to_ipv6_mapped());
+    assert_match_str::<Ipv6Addr>("1::", true);
+    assert_match_str::<Ipv6Addr>("1:2:3:4:5:6:7::", true);
+    assert_match_str::<Ipv6Addr>("::2:3:4:5:6:7:8", true);
+    assert_match_str::<Ipv6Addr>("1:2:3:4:5:6:1.2.3.4", true);
+    assert_match_str::<Ipv6Addr>("::1.2.3.4", true);
+    assert_match_str::<Ipv6Addr>("ABCD::ef", true);
+    assert_match_str::<Ipv6Addr>(":::", false);
+    assert_match_str::<Ipv6Addr>("1::2::3", false);
+    assert_match_str::<Ipv6Addr>("1:2:3:4:5:6:7", false);
+    assert_match_str::<Ipv6Addr>("1:2:3:4:5:6:7:8:9", false);
+    assert_match_str::<Ipv6Addr>("1:2:3:4::5:6:7:8", false);
+    assert_match_str::<Ipv6Addr>("12345::", false);
+    assert_match_str::<Ipv6Addr>("", false);
+}
+
+#[test]
+fn test_ip_addr() {
+    assert_match(IpAddr::from(Ipv4Addr::LOCALHOST));
+    assert_match(IpAddr::from(Ipv6Addr::LOCALHOST));
+    assert_match_str::<IpAddr>("1.2.3.256", false);
+    assert_match_str::<IpAddr>(":::", false);
+}
+
+#[test]
+fn test_socket_addr() {
+    assert_match(SocketAddrV4::new(Ipv4Addr::LOCALHOST, 8080));
+    assert_match_str::<SocketAddrV4>("1.2.3.4", false);
+    assert_match_str::<SocketAddrV4>("1.2.3.4:", false);
+    assert_match_str::<SocketAddrV4>("1.2.3.4:65535", true);
+    assert_match_str::<SocketAddrV4>("1.2.3.4:0080", true);
+    assert_match_str::<SocketAddrV4>("1.2.3.4:65536", false);
+
+    assert_match(SocketAddrV6::new(Ipv6Addr::LOCALHOST, 8080, 0, 0));
+    assert_match(SocketAddrV6::new(Ipv6Addr::LOCALHOST, 8080, 0, 3));
+    assert_match_str::<SocketAddrV6>("::1:80", false);
+    assert_match_str::<SocketAddrV6>("[::1]", false);
+    assert_match_str::<SocketAddrV6>("[::1]:65536", false);
+    assert_match_str::<SocketAddrV6>("[::1%4294967296]:80", false);
+
+    assert_match(SocketAddr::from((Ipv4Addr::LOCALHOST, 80)));
+    assert_match(SocketAddr::from((Ipv6Addr::LOCALHOST, 80)));
+    assert_match_str::<SocketAddr>("1.2.3.4", false);
+}
+
+#[test]
+fn test_int_range() {
+    for value in u8::MIN..=u8::MAX {
+        assert_match(value);
+    }
+    for value in i8::MIN..=i8::MAX {
+        assert_match(value);
+    }
+    for value in 1..=u8::MAX {
+        assert_match(NonZero::new(value).unwrap());
+    }
+    for value in i8::MIN..=i8::MAX {
+        if let Some(value) = NonZero::new(value) {
+            assert_match(value);
+        }
+    }
+    assert_match_str::<u8>("256", false);
+    assert_match_str::<u8>("1000", false);
+    assert_match_str::<u8>("+255", true);
+    assert_match_str::<u8>("000255", true);
+    assert_match_str::<u8>("-0", false);
+    assert_match_str::<u8>("+", false);
+    assert_match_str::<i8>("128", false);
+    assert_match_str::<i8>("-129", false);
+    assert_match_str::<i8>("-0", true);
+    assert_match_str::<i8>("+0", true);
+    assert_match_str::<i8>("-00128", true);
+    assert_match_str::<NonZero<u8>>("0", false);
+    assert_match_str::<NonZero<u8>>("000", false);
+    assert_match_str::<NonZero<u8>>("001", true);
+    assert_match_str::<NonZero<i8>>("-0", false);
+
+    assert_match_str::<u16>("65535", true);
+    assert_match_str::<u16>("65536", false);
+    assert_match_str::<u16>("65600", false);
+    assert_match_str::<u16>("70000", false);
+    assert_match_str::<u16>("9999", true);
+    assert_match_str::<u128>("340282366920938463463374607431768211456", false);
+    assert_match_str::<i128>("-170141183460469231731687303715884105729", false);
+    assert_match_str::<i128>("170141183460469231731687303715884105728", false);
+}
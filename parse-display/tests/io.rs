@@ -0,0 +1,88 @@
+#![cfg(feature = "std")]
+
+use std::io::{BufRead, BufReader, Read};
+
+use parse_display::{
+    FromStr, ParseError,
+    io::{LineErrorKind, parse_lines},
+};
+
+#[derive(FromStr, Debug, Eq, PartialEq)]
+#[display("{name}={value}")]
+struct Record {
+    name: String,
+    value: u32,
+}
+
+fn record(name: &str, value: u32) -> Record {
+    Record {
+        name: name.into(),
+        value,
+    }
+}
+
+#[test]
+fn parse_lines_ok() {
+    let values: Vec<_> = parse_lines::<Record, _>("a=1\nb=2\r\nc=3".as_bytes())
+        .map(Result::unwrap)
+        .collect();
+    assert_eq!(values, vec![record("a", 1), record("b", 2), record("c", 3)]);
+}
+
+#[test]
+fn parse_lines_error() {
+    let mut lines = parse_lines::<Record, _>("a=1\nb=x\nc=3\n".as_bytes());
+    assert_eq!(lines.next().unwrap().unwrap(), record("a", 1));
+    let e = lines.next().unwrap().unwrap_err();
+    assert_eq!(e.line_number(), 2);
+    assert_eq!(e.line(), "b=x");
+    assert_eq!(e.parse_error().unwrap().field(), Some("value"));
+    assert_eq!(e.to_string(), "line 2: parse failed. (`b=x`)");
+    assert_eq!(lines.next().unwrap().unwrap(), record("c", 3));
+    assert!(lines.next().is_none());
+}
+
+#[test]
+fn parse_lines_blank_and_comment() {
+    let input = "a=1\n\n  \n# comment\n  # indented\nb=2\n";
+    let values: Vec<_> = parse_lines::<Record, _>(input.as_bytes())
+        .skip_blank_lines()
+        .skip_comment_lines()
+        .map(Result::unwrap)
+        .collect();
+    assert_eq!(values, vec![record("a", 1), record("b", 2)]);
+
+    let line_numbers: Vec<_> = parse_lines::<Record, _>(input.as_bytes())
+        .filter_map(|r| r.err())
+        .map(|e| e.line_number())
+        .collect();
+    assert_eq!(line_numbers, vec![2, 3, 4, 5]);
+}
+
+#[test]
+fn parse_lines_io_error() {
+    struct Failing;
+    impl Read for Failing {
+        fn read(&mut self, _: &mut [u8]) -> std::io::Result<usize> {
+            Err(std::io::Error::other("broken"))
+        }
+    }
+    let reader: Box<dyn BufRead> = Box::new(BufReader::new(Failing));
+    let mut lines = parse_lines::<Record, _>(reader);
+    let e = lines.next().unwrap().unwrap_err();
+    assert_eq!(e.line_number(), 1);
+    assert!(matches!(e.kind(), LineErrorKind::Io(_)));
+    assert_eq!(e.to_string(), "line 1: read failed.");
+    assert_eq!(std::error::Error::source(&e).unwrap().to_string(), "broken");
+    assert!(lines.next().is_none());
+}
+
+#[test]
+fn parse_lines_source() {
+    let e = parse_lines::<Record, _>("a".as_bytes())
+        .next()
+        .unwrap()
+        .unwrap_err();
+    let source = std::error::Error::source(&e).unwrap();
+    assert!(source.downcast_ref::<ParseError>().is_some());
+}
@@ -0,0 +1,71 @@
+use parse_display::{ParseError, scan};
+
+#[test]
+fn scan_tuple() {
+    let (a, b): (u32, String) = scan!("10-abc", "{}-{}").unwrap();
+    assert_eq!(a, 10);
+    assert_eq!(b, "abc");
+}
+
+#[test]
+fn scan_typed() {
+    let (x, y) = scan!("1,2", "{x:u32},{y:u32}").unwrap();
+    assert_eq!((x, y), (1, 2));
+
+    let (a, b) = scan!("1,2", "{:u8},{:i64}").unwrap();
+    assert_eq!((a, b), (1u8, 2i64));
+}
+
+#[test]
+fn scan_single() {
+    let n: u8 = scan!("n=5", "n={}").unwrap();
+    assert_eq!(n, 5);
+}
+
+#[test]
+fn scan_no_placeholder() {
+    let r: Result<(), ParseError> = scan!("abc", "abc");
+    assert!(r.is_ok());
+    assert!(scan!("abd", "abc").is_err());
+}
+
+#[test]
+fn scan_same_name() {
+    let r = scan!("1-1", "{x:u32}-{x}");
+    assert_eq!(r, Ok(1));
+}
+
+#[test]
+fn scan_escape() {
+    let (a, b) = scan!("{1}-{2}", "{{{:u32}}}-{{{:u32}}}").unwrap();
+    assert_eq!((a, b), (1, 2));
+}
+
+#[test]
+fn scan_string_input() {
+    let input = String::from("3/4");
+    let (a, b) = scan!(input, "{:u32}/{:u32}").unwrap();
+    assert_eq!((a, b), (3, 4));
+}
+
+#[test]
+fn scan_error() {
+    let e = scan!("1-x", "{a:u32}-{b:u32}").unwrap_err();
+    assert_eq!(e.field(), Some("1"));
+}
+
+#[test]
+fn scan_generic() {
+    fn f<T: core::str::FromStr>(s: &str) -> Option<(T, T)> {
+        scan!(s, "{:T},{:T}").ok()
+    }
+    assert_eq!(f::<u32>("1,2"), Some((1, 2)));
+    assert_eq!(f::<u32>("1,x"), None);
+}
+
+#[test]
+fn scan_question_mark() -> Result<(), ParseError> {
+    let (a, b): (u32, u32) = scan!("1 2", "{} {}")?;
+    assert_eq!((a, b), (1, 2));
+    Ok(())
+}